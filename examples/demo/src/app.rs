@@ -1,9 +1,5 @@
 use hframe::Aware;
 
-const IFRAME: &str = r#"
-<iframe src="https://www.example.com/"></iframe>
-"#;
-
 const VIDEO: &str = r#"
 <video controls>
     <source src="https://www.w3schools.com/html/mov_bbb.mp4" type="video/mp4">
@@ -12,10 +8,6 @@ const VIDEO: &str = r#"
 </video>
 "#;
 
-const YT: &str = r#"
-<iframe width="1280" height="720" src="https://www.youtube.com/embed/PCp2iXA1uLE" title="FREDERIC 「oddloop」Music Video" frameborder="0" allow="accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture; web-share" allowfullscreen></iframe>
-"#;
-
 const COUNTER_TEMPLATE: &str = r#"
 <div style="display: flex; justify-content: center; align-items: center; padding: 8px; color: red; font: 36px sans-serif;">
     <span>{count}</span>
@@ -92,7 +84,9 @@ impl eframe::App for App {
             .show(ctx);
 
         hframe::HtmlWindow::new("Iframe")
-            .content(IFRAME)
+            .url("https://www.example.com/")
+            .loading_indicator(true)
+            .navigation_bar(true)
             .open(&mut self.iframe_open)
             .show(ctx);
 
@@ -101,7 +95,12 @@ impl eframe::App for App {
         }
 
         hframe::HtmlWindow::new("YT")
-            .content(YT)
+            .iframe(
+                hframe::Iframe::new("https://www.youtube.com/embed/PCp2iXA1uLE")
+                    .title("FREDERIC 「oddloop」Music Video")
+                    .allow("accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture; web-share; fullscreen"),
+            )
+            .loading_indicator(true)
             .open(&mut self.yt_open)
             .show(ctx);
 
@@ -0,0 +1,54 @@
+/// Shape of an egui area, used to punch holes in the HTML content below it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AreaShape {
+    /// The area rect with rounded corners, surrounded by a shadow. This is how
+    /// egui frames windows and popups.
+    Frame {
+        rounding: egui::Rounding,
+        /// How much the shadow extends outside the rect. Only punched if
+        /// `set_shadow_holes` is enabled.
+        shadow: f32,
+    },
+    /// A closed polygon in egui coordinates, for areas painting anything else.
+    Polygon(Vec<egui::Pos2>),
+}
+
+impl AreaShape {
+    /// A plain rect, like the one of frameless areas.
+    pub fn rect() -> Self {
+        Self::Frame {
+            rounding: egui::Rounding::ZERO,
+            shadow: 0.0,
+        }
+    }
+
+    /// The shape of the given egui frame.
+    pub fn frame(frame: &egui::Frame) -> Self {
+        Self::Frame {
+            rounding: frame.rounding,
+            shadow: frame.shadow.extrusion,
+        }
+    }
+
+    /// The frame of egui windows with the given style.
+    pub fn window(style: &egui::Style) -> Self {
+        Self::frame(&egui::Frame::window(style))
+    }
+
+    /// The frame of egui popups, menus and tooltips with the given style.
+    pub fn popup(style: &egui::Style) -> Self {
+        Self::Frame {
+            rounding: style.visuals.menu_rounding,
+            shadow: style.visuals.popup_shadow.extrusion,
+        }
+    }
+
+    /// Best guess for an egui area painted in the given order.
+    pub(crate) fn for_order(order: egui::Order, style: &egui::Style) -> Self {
+        match order {
+            egui::Order::Middle => Self::window(style),
+            egui::Order::Foreground | egui::Order::Tooltip => Self::popup(style),
+            _ => Self::rect(),
+        }
+    }
+}
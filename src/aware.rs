@@ -1,4 +1,4 @@
-use crate::{get_composition_context, ComposedArea};
+use crate::{get_composition_context, AreaShape};
 
 /// Allows you to implement `aware` for egui entities so hframe can know about
 /// their existence when applying compositions.
@@ -6,23 +6,81 @@ pub trait Aware {
     /// Let hframe know about the existence of this entity.
     ///
     /// You must call this in anything from the egui world that can overlap
-    /// with HTML content (like normal egui windows).
+    /// with HTML content (like normal egui windows), unless `set_auto_aware`
+    /// is enabled.
     fn aware(self) -> Self;
+
+    /// Like `aware`, but punching holes with the given shape instead of the
+    /// one of egui frames.
+    fn aware_with_shape(self, shape: AreaShape) -> Self;
 }
 
+/// Responses of egui windows.
 impl<R> Aware for Option<egui::InnerResponse<R>> {
     fn aware(self) -> Self {
-        let inner_response = self?;
-        let egui_ctx = &inner_response.response.ctx;
-        let cmp = get_composition_context(egui_ctx);
-        let mut cmp = cmp.lock().unwrap();
-
-        cmp.put_composed_area(ComposedArea {
-            id: inner_response.response.layer_id.id,
-            rect: inner_response.response.rect,
-            html: None,
-        });
-
-        Some(inner_response)
+        let response = self?;
+        let shape = AreaShape::window(&response.response.ctx.style());
+        Some(response.aware_with_shape(shape))
+    }
+
+    fn aware_with_shape(self, shape: AreaShape) -> Self {
+        Some(self?.aware_with_shape(shape))
+    }
+}
+
+/// Responses of egui areas, like `egui::Area::show`, as well as widgets
+/// opening popups, like `egui::ComboBox::show_ui`.
+///
+/// The area where the response lives and every open popup, menu or tooltip
+/// are made aware.
+impl<R> Aware for egui::InnerResponse<R> {
+    fn aware(self) -> Self {
+        aware_inner_response(self, None)
+    }
+
+    fn aware_with_shape(self, shape: AreaShape) -> Self {
+        aware_inner_response(self, Some(shape))
+    }
+}
+
+fn aware_inner_response<R>(
+    inner_response: egui::InnerResponse<R>,
+    shape: Option<AreaShape>,
+) -> egui::InnerResponse<R> {
+    let response = &inner_response.response;
+    let cmp = get_composition_context(&response.ctx);
+    let mut cmp = cmp.lock().unwrap();
+
+    // The response may belong to a widget inside the area, like a combo box
+    // in a window.
+    let layer_id = response.layer_id;
+    let rect = response
+        .ctx
+        .memory(|mem| mem.area_rect(layer_id.id))
+        .unwrap_or(response.rect);
+
+    cmp.put_aware_area(layer_id, rect, shape);
+    cmp.put_popup_areas(None);
+    drop(cmp);
+
+    inner_response
+}
+
+/// Widget responses that may show popups, like the ones returned by
+/// `Response::on_hover_ui` or `Response::context_menu`, and menu buttons.
+///
+/// Every open popup, menu or tooltip is made aware, but not the widget itself.
+/// With `aware_with_shape`, all of them get the given shape.
+impl Aware for egui::Response {
+    fn aware(self) -> Self {
+        let cmp = get_composition_context(&self.ctx);
+        cmp.lock().unwrap().put_popup_areas(None);
+        self
+    }
+
+    fn aware_with_shape(self, shape: AreaShape) -> Self {
+        let cmp = get_composition_context(&self.ctx);
+        cmp.lock().unwrap().put_popup_areas(Some(shape));
+        self
     }
 }
@@ -0,0 +1,53 @@
+/// Maps egui coordinates (points) to CSS pixels in the document.
+///
+/// Accounts for the canvas position in the page, egui zoom, the device pixel
+/// ratio and any CSS scaling applied to the canvas.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanvasTransform {
+    /// Position of the canvas in the document, in CSS pixels.
+    pub offset: egui::Vec2,
+    /// CSS pixels per egui point.
+    pub scale: f32,
+}
+
+impl CanvasTransform {
+    pub const IDENTITY: Self = Self {
+        offset: egui::Vec2::ZERO,
+        scale: 1.0,
+    };
+
+    /// Computes the transform from the rect of the canvas in the document.
+    ///
+    /// Without a canvas, the egui zoom is the best guess for the scale.
+    pub(crate) fn new(egui_ctx: &egui::Context, canvas_rect: Option<egui::Rect>) -> Self {
+        match canvas_rect {
+            Some(canvas_rect) => {
+                let screen_rect = egui_ctx.screen_rect();
+                let scale = if screen_rect.width() > 0.0 {
+                    canvas_rect.width() / screen_rect.width()
+                } else {
+                    1.0
+                };
+
+                Self {
+                    offset: canvas_rect.min.to_vec2(),
+                    scale,
+                }
+            }
+            None => Self {
+                offset: egui::Vec2::ZERO,
+                scale: egui_ctx.zoom_factor(),
+            },
+        }
+    }
+
+    /// Maps a position in points to a position in the document.
+    pub fn to_css_pos(&self, pos: egui::Pos2) -> egui::Pos2 {
+        (pos.to_vec2() * self.scale + self.offset).to_pos2()
+    }
+
+    /// Maps a rect in points to a rect in the document.
+    pub fn to_css_rect(&self, rect: egui::Rect) -> egui::Rect {
+        egui::Rect::from_min_max(self.to_css_pos(rect.min), self.to_css_pos(rect.max))
+    }
+}
@@ -1,62 +1,171 @@
-use web_sys::wasm_bindgen::JsCast;
+use crate::{AreaShape, CanvasTransform, Iframe, LoadState};
 
-pub(crate) struct ComposedArea {
-    /// The egui id used to track this area. It should be a LayerId's id, not a
-    /// widget id.
+/// An egui area known by hframe, optionally holding HTML content.
+pub struct ComposedArea {
+    /// The egui id used to track this area. For egui areas like windows, it's
+    /// their LayerId's id.
     pub(crate) id: egui::Id,
+    /// The egui layer where this area is painted. Many areas may share it.
+    pub(crate) layer_id: egui::LayerId,
     /// Whole rect of the egui rendered area.
     pub(crate) rect: egui::Rect,
+    /// Shape of the area inside `rect`.
+    pub(crate) shape: AreaShape,
     pub(crate) html: Option<ComposedHtml>,
 }
 
-pub(crate) struct ComposedHtml {
+impl ComposedArea {
+    /// The egui id used to track this area.
+    pub fn id(&self) -> egui::Id {
+        self.id
+    }
+
+    /// The egui layer where this area is painted.
+    pub fn layer_id(&self) -> egui::LayerId {
+        self.layer_id
+    }
+
+    /// Whole rect of the egui rendered area.
+    pub fn rect(&self) -> egui::Rect {
+        self.rect
+    }
+
+    /// Shape of the area inside its rect.
+    pub fn shape(&self) -> &AreaShape {
+        &self.shape
+    }
+
+    /// The HTML content of this area, if any.
+    pub fn html(&self) -> Option<&ComposedHtml> {
+        self.html.as_ref()
+    }
+}
+
+/// HTML content placed inside a [`ComposedArea`].
+pub struct ComposedHtml {
     pub(crate) id: String,
     pub(crate) content: String,
     pub(crate) status: ComposedHtmlStatus,
     /// Rect where the HTML content should be placed.
     pub(crate) rect: egui::Rect,
+    /// Only the part of the HTML content inside this rect is shown, like egui
+    /// does with widgets inside scroll areas.
+    pub(crate) clip_rect: egui::Rect,
+    /// Keep the element, hidden, when the area stops being shown.
+    pub(crate) keep_alive: bool,
+    /// Origins of iframes allowed to send messages, besides the page one.
+    pub(crate) allowed_origins: Vec<String>,
+    /// The iframe making up the content, if built with [`Iframe`]. Only its
+    /// loading state is tracked.
+    pub(crate) iframe: Option<Iframe>,
+    /// Loading state of `iframe`, set by the composition context.
+    pub(crate) load_state: Option<LoadState>,
+    /// egui time when `iframe` started loading.
+    pub(crate) load_started: f64,
+    /// Title of the document of the iframe making up the content, reported
+    /// by same-origin iframes.
+    pub(crate) title: Option<String>,
 }
 
 impl ComposedHtml {
-    pub(crate) fn to_outer_html(&self) -> String {
+    /// Id of the HTML element holding the content.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The HTML content.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Rect where the HTML content is placed.
+    pub fn rect(&self) -> egui::Rect {
+        self.rect
+    }
+
+    /// Only the part of `rect` inside this rect is shown.
+    pub fn clip_rect(&self) -> egui::Rect {
+        self.clip_rect
+    }
+
+    /// The part of `rect` that is shown, ignoring areas on top of it.
+    pub fn visible_rect(&self) -> egui::Rect {
+        self.rect.intersect(self.clip_rect)
+    }
+
+    /// Whether the element is kept, hidden, when the area stops being shown.
+    pub fn keep_alive(&self) -> bool {
+        self.keep_alive
+    }
+
+    /// Origins of iframes allowed to send messages, besides the page one.
+    pub fn allowed_origins(&self) -> &[String] {
+        &self.allowed_origins
+    }
+
+    /// Loading state of the iframe making up the content, if tracked.
+    pub fn load_state(&self) -> Option<&LoadState> {
+        self.load_state.as_ref()
+    }
+
+    /// Title of the document of the iframe making up the content, if
+    /// reported.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Whether a message from the given origin is accepted. `None` stands for
+    /// the page origin.
+    pub(crate) fn accepts_origin(&self, origin: Option<&str>) -> bool {
+        origin.map_or(true, |origin| {
+            self.allowed_origins
+                .iter()
+                .any(|allowed| allowed == "*" || allowed == origin)
+        })
+    }
+
+    /// Display state for the current frame.
+    pub fn status(&self) -> &ComposedHtmlStatus {
+        &self.status
+    }
+
+    pub(crate) fn to_outer_html(&self, transform: &CanvasTransform) -> String {
         let id = &self.id;
         let content = &self.content;
-        let styles = self.to_styles();
+        let styles = self.to_styles(transform);
 
         format!(r#"<div id="{id}" class="hframe-composed-area" style="{styles}">{content}</div>"#)
     }
 
-    pub(crate) fn to_styles(&self) -> String {
-        let top = self.rect.min.y;
-        let left = self.rect.min.x;
-        let width = self.rect.width();
-        let height = self.rect.height();
+    pub(crate) fn to_styles(&self, transform: &CanvasTransform) -> String {
+        let rect = transform.to_css_rect(self.rect);
+        let top = rect.min.y;
+        let left = rect.min.x;
+        let width = rect.width();
+        let height = rect.height();
         let status = self.status.to_styles();
 
         format!("top: {top}px; left: {left}px; width: {width}px; height: {height}px; {status}")
     }
-
-    pub(crate) fn get_element(&self) -> web_sys::Element {
-        let window = web_sys::window().unwrap();
-        let document = window.document().unwrap();
-        document
-            .get_element_by_id(&self.id)
-            .expect("Composed HTML area was not found in the document")
-    }
-
-    pub(crate) fn get_html_element(&self) -> web_sys::HtmlElement {
-        self.get_element()
-            .dyn_into::<web_sys::HtmlElement>()
-            .unwrap()
-    }
 }
 
-pub(crate) struct ComposedHtmlStatus {
+/// Display state of a [`ComposedHtml`] for the current frame.
+pub struct ComposedHtmlStatus {
     pub(crate) visible: bool,
     pub(crate) interactive: bool,
 }
 
 impl ComposedHtmlStatus {
+    /// Whether the HTML element is shown, instead of hidden with CSS.
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Whether the HTML element receives pointer events.
+    pub fn interactive(&self) -> bool {
+        self.interactive
+    }
+
     pub(crate) fn to_styles(&self) -> String {
         let interactive = if self.interactive {
             ""
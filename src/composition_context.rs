@@ -1,166 +1,750 @@
-use crate::{composition_strategies, utils, ComposedArea, CompositionStrategy};
+use crate::{
+    composition_strategies, dom_backends, utils, AreaShape, BrowserEngine, CanvasTransform,
+    ComposedArea, ComposedHtml, CompositionStrategy, DomBackend, HframeError, HtmlEvent,
+    HtmlMessage, HtmlStatus, LoadState, Navigation,
+};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     ops::Deref,
     sync::{Arc, Mutex},
+    time::Duration,
 };
-use web_sys::wasm_bindgen::JsValue;
 
 const GLOBAL_STYLES_ID: &str = "hframe-global-styles";
+const GLOBAL_SCRIPT_ID: &str = "hframe-global-script";
+
+/// How long iframes may take to load before being considered failed.
+const DEFAULT_LOAD_TIMEOUT: Duration = Duration::from_secs(30);
 
-pub(crate) struct CompositionContext {
+/// Shared so it can be called once the context is unlocked.
+type ErrorCallback = Arc<Mutex<Box<dyn FnMut(HframeError) + Send>>>;
+
+/// Tracks the areas known by hframe and composes them on every `sync`.
+pub struct CompositionContext {
     // Hope this doesn't cause a cycle reference. I don't see right know a way
     // to make this weak.
     pub(crate) egui_ctx: egui::Context,
     composed_areas: Vec<ComposedArea>,
     composed_areas_since_last_sync: HashSet<egui::Id>,
+    /// Areas not shown anymore whose HTML element is kept alive, hidden.
+    kept_alive_areas: Vec<ComposedArea>,
+    /// Messages to deliver to HTML elements, by id, on the next `sync`.
+    outgoing_messages: Vec<(String, String)>,
+    /// Iframe navigations, by id, to perform on the next `sync`.
+    navigations: Vec<(String, Navigation)>,
+    /// JSON messages sent by HTML content, by id, during the last frame.
+    inbox: HashMap<String, Vec<String>>,
+    /// DOM events happened inside HTML content, by id, during the last frame.
+    events: HashMap<String, Vec<HtmlEvent>>,
     /// `dyn` to support setting a strategy with a runtime criteria.
     composition_strategy: Option<Box<dyn CompositionStrategy>>,
+    dom: Box<dyn DomBackend>,
+    /// Ids of HTML elements that went missing and must be recreated.
+    broken_html_ids: HashSet<String>,
+    error_callback: Option<ErrorCallback>,
+    /// Errors waiting to be passed to the error callback at the end of
+    /// `sync`.
+    pending_errors: Vec<HframeError>,
+    canvas_id: Option<String>,
+    /// Updated on every `sync`.
+    canvas_transform: CanvasTransform,
+    auto_aware: bool,
+    shadow_holes: bool,
+    load_timeout: Duration,
 }
 
 impl CompositionContext {
     pub(crate) fn new(egui_ctx: &egui::Context) -> Self {
-        let document = web_sys::window().unwrap().document().unwrap();
-
-        let style = document.get_element_by_id(GLOBAL_STYLES_ID);
-
-        if style.is_none() {
-            document
-                .head()
-                .expect("No head element found in the document")
-                .insert_adjacent_html(
-                    "beforeend",
-                    &format!(
-                        "<style id=\"{}\">{}</style>",
-                        GLOBAL_STYLES_ID,
-                        include_str!("hframe.css")
-                    ),
-                )
-                .unwrap();
-        }
+        let mut cmp = Self {
+            egui_ctx: egui_ctx.clone(),
+            composed_areas: Vec::new(),
+            composed_areas_since_last_sync: HashSet::new(),
+            kept_alive_areas: Vec::new(),
+            outgoing_messages: Vec::new(),
+            navigations: Vec::new(),
+            inbox: HashMap::new(),
+            events: HashMap::new(),
+            composition_strategy: None,
+            dom: Box::new(dom_backends::Noop),
+            broken_html_ids: HashSet::new(),
+            error_callback: None,
+            pending_errors: Vec::new(),
+            canvas_id: None,
+            canvas_transform: CanvasTransform::IDENTITY,
+            auto_aware: false,
+            shadow_holes: false,
+            load_timeout: DEFAULT_LOAD_TIMEOUT,
+        };
 
-        let composition_strategy: Box<dyn CompositionStrategy> =
-            Box::new(composition_strategies::SvgDataMask::new());
+        cmp.set_dom_backend(dom_backends::default_backend());
 
-        web_sys::console::debug_2(
-            &JsValue::from("Using composition strategy:"),
-            &JsValue::from(composition_strategy.name()),
+        let engine = BrowserEngine::detect();
+        cmp.set_composition_strategy(
+            composition_strategies::for_browser_engine(engine),
+            &format!("selected for {}", engine.name()),
         );
+        cmp
+    }
 
-        Self {
-            egui_ctx: egui_ctx.clone(),
-            composed_areas: Vec::new(),
-            composed_areas_since_last_sync: HashSet::new(),
-            composition_strategy: Some(composition_strategy),
+    /// Styles applied by the previous strategy don't need to be undone as the
+    /// style of every HTML element is rewritten on each frame.
+    pub(crate) fn set_composition_strategy(
+        &mut self,
+        strategy: Box<dyn CompositionStrategy>,
+        reason: &str,
+    ) {
+        self.dom.debug(
+            "Using composition strategy:",
+            &format!("{} ({reason})", strategy.name()),
+        );
+
+        self.composition_strategy = Some(strategy);
+    }
+
+    pub(crate) fn set_dom_backend(&mut self, dom: Box<dyn DomBackend>) {
+        self.dom = dom;
+
+        let result = self
+            .dom
+            .insert_style(GLOBAL_STYLES_ID, include_str!("hframe.css"))
+            .and_then(|_| {
+                self.dom
+                    .insert_script(GLOBAL_SCRIPT_ID, include_str!("hframe.js"))
+            });
+
+        if let Err(err) = result {
+            self.handle_error(err);
         }
     }
 
-    pub(crate) fn put_composed_area(&mut self, area: ComposedArea) {
+    pub(crate) fn set_error_callback(&mut self, callback: Box<dyn FnMut(HframeError) + Send>) {
+        self.error_callback = Some(Arc::new(Mutex::new(callback)));
+    }
+
+    pub(crate) fn set_canvas_id(&mut self, canvas_id: &str) {
+        self.canvas_id = Some(canvas_id.to_string());
+    }
+
+    pub(crate) fn set_auto_aware(&mut self, auto_aware: bool) {
+        self.auto_aware = auto_aware;
+    }
+
+    pub(crate) fn set_shadow_holes(&mut self, shadow_holes: bool) {
+        self.shadow_holes = shadow_holes;
+    }
+
+    pub(crate) fn set_load_timeout(&mut self, load_timeout: Duration) {
+        self.load_timeout = load_timeout;
+    }
+
+    /// Errors are queued for the error callback, or logged if there is none.
+    ///
+    /// The callback is not called right away, as it may call hframe while
+    /// the context is locked.
+    fn handle_error(&mut self, err: HframeError) {
+        match &self.error_callback {
+            Some(_) => self.pending_errors.push(err),
+            None => self.dom.debug("hframe error:", &err.to_string()),
+        }
+    }
+
+    /// Takes the queued errors along with the callback to pass them to.
+    fn take_pending_errors(&mut self) -> Option<(ErrorCallback, Vec<HframeError>)> {
+        let callback = self.error_callback.clone()?;
+        let errors = std::mem::take(&mut self.pending_errors);
+        (!errors.is_empty()).then_some((callback, errors))
+    }
+
+    /// Returns whether the HTML content of the area was (re-)rendered.
+    pub(crate) fn put_composed_area(&mut self, area: ComposedArea) -> bool {
+        // Bring kept alive areas back so their element is reused.
+        if let Some(index) = self.kept_alive_areas.iter().position(|a| a.id == area.id) {
+            let kept_alive = self.kept_alive_areas.remove(index);
+            self.composed_areas.push(kept_alive);
+        }
+
+        let area_id = area.id;
         let (new, prev) = utils::vec::insert_or_replace(&mut self.composed_areas, area, |a| a.id);
         self.composed_areas_since_last_sync.insert(new.id);
 
-        if let Some(new_html) = &new.html {
-            let did_content_change = prev
-                .as_ref()
-                .map(|prev| {
-                    prev.html
-                        .as_ref()
-                        .expect("Non HTML area turned into HTML area")
-                        .content
-                        != new_html.content
-                })
+        // Whether the element was created from scratch, reloading iframes.
+        let mut recreated = false;
+        let rendered = if let Some(new_html) = &new.html {
+            let prev_html = prev.as_ref().and_then(|prev| prev.html.as_ref());
+            let did_content_change = prev_html
+                .map(|prev_html| prev_html.content != new_html.content)
                 .unwrap_or(true);
 
-            let window = web_sys::window().unwrap();
-            let document = window.document().unwrap();
+            let was_broken = self.broken_html_ids.remove(&new_html.id);
+            let transform = self.canvas_transform;
+            let render = || {
+                self.dom
+                    .replace_element(&new_html.id, &new_html.to_outer_html(&transform))
+            };
+
+            let mut rendered = did_content_change || was_broken;
+            let result = if prev_html.is_none() || was_broken {
+                recreated = true;
+                render()
+            } else if did_content_change {
+                // Only the changed nodes are touched, so the rest keep their
+                // state.
+                self.dom
+                    .patch_element(&new_html.id, &new_html.to_outer_html(&transform))
+            } else {
+                match self
+                    .dom
+                    .set_attribute(&new_html.id, "style", &new_html.to_styles(&transform))
+                {
+                    // Removed by someone else, bring it back right away but
+                    // still report it.
+                    Err(err @ HframeError::ElementNotFound(_)) => {
+                        rendered = true;
+                        recreated = true;
+                        render().and(Err(err))
+                    }
+                    result => result,
+                }
+            };
+
+            if let Err(err) = result {
+                self.handle_error(err);
+            }
+
+            rendered
+        } else {
+            false
+        };
+
+        let prev_html = prev.and_then(|prev| prev.html);
+        let new_html = self
+            .composed_areas
+            .iter_mut()
+            .find(|area| area.id == area_id)
+            .and_then(|area| area.html.as_mut());
+
+        if let Some(new_html) = new_html {
+            // Iframes only load again if recreated or pointed to another
+            // document. Patching other attributes doesn't reload them.
+            // Kept until a new document is loaded.
+            new_html.title = prev_html.as_ref().and_then(|html| html.title.clone());
+
+            if let Some(iframe) = &new_html.iframe {
+                let same_document = prev_html
+                    .as_ref()
+                    .and_then(|html| html.iframe.as_ref())
+                    .is_some_and(|prev_iframe| prev_iframe.loads_same_document(iframe));
 
-            let element = document.get_element_by_id(&new_html.id).unwrap_or_else(|| {
-                let body = document.body().unwrap();
-                let element = document.create_element("div").unwrap();
-                body.append_child(&element).unwrap();
-                element
+                match prev_html.as_ref().filter(|_| same_document && !recreated) {
+                    Some(prev_html) => {
+                        new_html.load_state = prev_html.load_state.clone();
+                        new_html.load_started = prev_html.load_started;
+                    }
+                    None => start_loading(new_html, &self.egui_ctx),
+                }
+            }
+        }
+
+        rendered
+    }
+
+    /// Registers an egui area without HTML content. Without a shape, it's
+    /// guessed from the area order.
+    ///
+    /// Areas holding HTML content are left untouched, as their owner already
+    /// registers them. The background is ignored too, as nothing can be
+    /// behind it.
+    pub(crate) fn put_aware_area(
+        &mut self,
+        layer_id: egui::LayerId,
+        rect: egui::Rect,
+        shape: Option<AreaShape>,
+    ) {
+        let has_html = self
+            .composed_areas
+            .iter()
+            .any(|area| area.id == layer_id.id && area.html.is_some());
+
+        if !has_html && layer_id.order != egui::Order::Background {
+            let shape = shape
+                .unwrap_or_else(|| AreaShape::for_order(layer_id.order, &self.egui_ctx.style()));
+
+            self.put_composed_area(ComposedArea {
+                id: layer_id.id,
+                layer_id,
+                rect,
+                shape,
+                html: None,
             });
+        }
+    }
 
-            if did_content_change {
-                element.set_outer_html(&new_html.to_outer_html());
+    /// Registers every visible popup, menu and tooltip. They are all shown in
+    /// their own egui areas on top of windows.
+    pub(crate) fn put_popup_areas(&mut self, shape: Option<AreaShape>) {
+        let areas = self.visible_egui_areas(|layer_id| {
+            matches!(
+                layer_id.order,
+                egui::Order::Foreground | egui::Order::Tooltip
+            )
+        });
+
+        for (layer_id, rect) in areas {
+            self.put_aware_area(layer_id, rect, shape.clone());
+        }
+    }
+
+    fn visible_egui_areas(
+        &self,
+        filter: impl Fn(&egui::LayerId) -> bool,
+    ) -> Vec<(egui::LayerId, egui::Rect)> {
+        self.egui_ctx.memory(|mem| {
+            mem.layer_ids()
+                .filter(|layer_id| filter(layer_id) && mem.areas().is_visible(layer_id))
+                .filter_map(|layer_id| Some((layer_id, mem.area_rect(layer_id.id)?)))
+                .collect()
+        })
+    }
+
+    /// Registers every visible egui area not registered during this frame, as
+    /// if `aware` was called on it.
+    fn put_egui_areas(&mut self) {
+        let areas = self.visible_egui_areas(|layer_id| {
+            !self.composed_areas_since_last_sync.contains(&layer_id.id)
+        });
+
+        for (layer_id, rect) in areas {
+            self.put_aware_area(layer_id, rect, None);
+        }
+    }
+
+    /// Removes the HTML element with the given id, even if it's kept alive.
+    ///
+    /// If its area is shown again, the element is rendered from scratch.
+    pub(crate) fn evict(&mut self, html_id: &str) {
+        let has_html_id = |area: &ComposedArea| area.html.as_ref().is_some_and(|h| h.id == html_id);
+
+        let known = self.composed_areas.iter().any(has_html_id)
+            || self.kept_alive_areas.iter().any(has_html_id);
+        self.composed_areas.retain(|area| !has_html_id(area));
+        self.kept_alive_areas.retain(|area| !has_html_id(area));
+        self.broken_html_ids.remove(html_id);
+
+        if known {
+            if let Err(err) = self.dom.remove_element(html_id) {
+                self.handle_error(err);
+            }
+        }
+    }
+
+    pub(crate) fn post_message(&mut self, html_id: &str, json: String) {
+        self.outgoing_messages.push((html_id.to_string(), json));
+    }
+
+    /// Delivered after composing, so elements shown for the first time in
+    /// this frame already exist.
+    fn flush_messages(&mut self) {
+        for (html_id, json) in std::mem::take(&mut self.outgoing_messages) {
+            if let Err(err) = self.dom.post_message(&html_id, &json) {
+                self.handle_error(err);
+            }
+        }
+    }
+
+    pub(crate) fn navigate(&mut self, html_id: &str, navigation: Navigation) {
+        self.navigations.push((html_id.to_string(), navigation));
+    }
+
+    /// Performed after composing, like messages are delivered.
+    fn flush_navigations(&mut self) {
+        for (html_id, navigation) in std::mem::take(&mut self.navigations) {
+            match self.dom.navigate(&html_id, navigation) {
+                Ok(true) => {
+                    let egui_ctx = self.egui_ctx.clone();
+                    let html = self.find_html_mut(&html_id);
+                    if let Some(html) = html.filter(|html| html.load_state.is_some()) {
+                        start_loading(html, &egui_ctx);
+                    }
+                }
+                Ok(false) => {}
+                Err(err) => self.handle_error(err),
+            }
+        }
+    }
+
+    /// Unread messages and events are dropped, they were available for a
+    /// whole frame.
+    fn receive_messages(&mut self) {
+        self.inbox.clear();
+        self.events.clear();
+
+        let messages = self.dom.take_messages().and_then(|messages| {
+            let events = self.dom.take_events()?;
+            let status = self.dom.take_status()?;
+            Ok((messages, events, status))
+        });
+        let (messages, events, status) = match messages {
+            Ok(messages) => messages,
+            Err(err) => return self.handle_error(err),
+        };
+
+        for message in messages {
+            if self.accepts_message(&message) {
+                self.inbox.entry(message.id).or_default().push(message.json);
+            }
+        }
+
+        for event in events {
+            if !self.accepts_message(&event) {
+                continue;
+            }
+
+            let parsed: HtmlEvent = match serde_json::from_str(&event.json) {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    self.handle_error(HframeError::Serialization(err.to_string()));
+                    continue;
+                }
+            };
+
+            self.events.entry(event.id).or_default().push(parsed);
+        }
+
+        for update in status {
+            if !self.accepts_message(&update) {
+                continue;
+            }
+
+            match serde_json::from_str(&update.json) {
+                Ok(parsed) => self.update_status(&update.id, parsed),
+                Err(err) => self.handle_error(HframeError::Serialization(err.to_string())),
+            }
+        }
+    }
+
+    fn update_status(&mut self, html_id: &str, status: HtmlStatus) {
+        let Some(html) = self.find_html_mut(html_id) else {
+            return;
+        };
+
+        match status {
+            // The title of the new document, if accessible, is reported right
+            // after it loads. Cross-origin documents never report one.
+            HtmlStatus::Load => {
+                html.title = None;
+                if html.load_state.is_some() {
+                    html.load_state = Some(LoadState::Loaded);
+                }
+            }
+            HtmlStatus::Error if html.load_state.is_some() => {
+                html.load_state = Some(LoadState::Failed("The iframe failed to load".into()));
+            }
+            HtmlStatus::Error => {}
+            HtmlStatus::Title { title } => html.title = Some(title),
+        }
+    }
+
+    /// Iframes still loading after the timeout are considered failed. Until
+    /// then, a repaint is scheduled for when they time out, as egui may be
+    /// idle.
+    fn time_out_loads(&mut self) {
+        let egui_ctx = self.egui_ctx.clone();
+        let now = egui_ctx.input(|i| i.time);
+        let timeout = self.load_timeout.as_secs_f64();
+
+        let loading = self
+            .composed_areas
+            .iter_mut()
+            .chain(&mut self.kept_alive_areas)
+            .filter_map(|area| area.html.as_mut())
+            .filter(|html| html.load_state == Some(LoadState::Loading));
+
+        for html in loading {
+            let elapsed = now - html.load_started;
+
+            if elapsed >= timeout {
+                let reason = format!("Not loaded after {timeout} seconds");
+                html.load_state = Some(LoadState::Failed(reason));
             } else {
-                element
-                    .set_attribute("style", &new_html.to_styles())
-                    .unwrap();
+                egui_ctx.request_repaint_after(Duration::from_secs_f64(timeout - elapsed));
+            }
+        }
+    }
+
+    fn find_html_mut(&mut self, html_id: &str) -> Option<&mut ComposedHtml> {
+        self.composed_areas
+            .iter_mut()
+            .chain(&mut self.kept_alive_areas)
+            .filter_map(|area| area.html.as_mut())
+            .find(|html| html.id == html_id)
+    }
+
+    /// Loading state of the iframe making up the HTML content with the given
+    /// id, if tracked.
+    pub(crate) fn load_state(&self, html_id: &str) -> Option<LoadState> {
+        self.composed_areas
+            .iter()
+            .chain(&self.kept_alive_areas)
+            .filter_map(|area| area.html.as_ref())
+            .find(|html| html.id == html_id)
+            .and_then(|html| html.load_state.clone())
+    }
+
+    /// Title of the document inside the HTML content with the given id, if
+    /// reported.
+    pub(crate) fn content_title(&self, html_id: &str) -> Option<&str> {
+        self.composed_areas
+            .iter()
+            .chain(&self.kept_alive_areas)
+            .filter_map(|area| area.html.as_ref())
+            .find(|html| html.id == html_id)
+            .and_then(|html| html.title.as_deref())
+    }
+
+    /// Only messages for known HTML content, from allowed origins, are
+    /// accepted.
+    fn accepts_message(&self, message: &HtmlMessage) -> bool {
+        self.composed_areas
+            .iter()
+            .chain(&self.kept_alive_areas)
+            .filter_map(|area| area.html.as_ref())
+            .find(|html| html.id == message.id)
+            .is_some_and(|html| html.accepts_origin(message.origin.as_deref()))
+    }
+
+    pub(crate) fn take_events(&mut self, html_id: &str) -> Vec<HtmlEvent> {
+        self.events.remove(html_id).unwrap_or_default()
+    }
+
+    pub(crate) fn take_messages<T: serde::de::DeserializeOwned>(
+        &mut self,
+        html_id: &str,
+    ) -> Vec<T> {
+        let mut messages = Vec::new();
+
+        for json in self.inbox.remove(html_id).unwrap_or_default() {
+            match serde_json::from_str(&json) {
+                Ok(message) => messages.push(message),
+                Err(err) => self.handle_error(HframeError::Serialization(err.to_string())),
             }
         }
+
+        messages
     }
 
     fn purge_composed_areas(&mut self) {
-        self.composed_areas.retain(|a| {
-            if !self.composed_areas_since_last_sync.contains(&a.id) {
-                if let Some(html) = &a.html {
-                    let window = web_sys::window().unwrap();
-                    let document = window.document().unwrap();
-                    let element = document.get_element_by_id(&html.id).unwrap();
-                    element.remove();
-                }
-                false
+        let mut errors = Vec::new();
+
+        let (shown, not_shown): (Vec<_>, Vec<_>) = std::mem::take(&mut self.composed_areas)
+            .into_iter()
+            .partition(|a| self.composed_areas_since_last_sync.contains(&a.id));
+        self.composed_areas = shown;
+
+        for area in not_shown {
+            let Some(html) = &area.html else {
+                continue;
+            };
+
+            let result = if html.keep_alive {
+                self.dom.set_style_property(&html.id, "display", "none")
             } else {
-                true
+                self.dom.remove_element(&html.id)
+            };
+
+            if let Err(err) = result {
+                errors.push(err);
+            } else if html.keep_alive {
+                self.kept_alive_areas.push(area);
             }
-        });
+        }
 
         self.composed_areas_since_last_sync.clear();
+
+        // Removed elements are created from scratch if shown again.
+        let html_ids: HashSet<_> = self
+            .composed_areas
+            .iter()
+            .chain(&self.kept_alive_areas)
+            .filter_map(|area| area.html.as_ref())
+            .map(|html| html.id.as_str())
+            .collect();
+        self.broken_html_ids
+            .retain(|html_id| html_ids.contains(html_id.as_str()));
+
+        for err in errors {
+            self.handle_error(err);
+        }
+    }
+
+    /// The canvas may move or be resized because of page scroll, layout or
+    /// zoom changes. Styles are rewritten every frame so the new transform is
+    /// picked up on the next one.
+    fn update_canvas_transform(&mut self) {
+        match self.dom.canvas_rect(self.canvas_id.as_deref()) {
+            Ok(rect) => self.canvas_transform = CanvasTransform::new(&self.egui_ctx, rect),
+            Err(err) => self.handle_error(err),
+        }
     }
 
     fn sort_composed_areas(&mut self) {
+        // Panels are painted in the background layer, which egui registers as
+        // an area every frame, so it's always first here.
         let layer_ids: Vec<_> = self.egui_ctx.memory(|mem| mem.layer_ids().collect());
 
         let mut composed_areas = std::mem::take(&mut self.composed_areas);
+        // Many areas may share a layer, so their relative order is kept.
         self.composed_areas = layer_ids
             .iter()
-            .filter_map(|layer_id| {
-                composed_areas
-                    .iter()
-                    .position(|area| area.id == layer_id.id)
-                    .map(|pos| composed_areas.swap_remove(pos))
+            .flat_map(|layer_id| {
+                let (in_layer, rest) = std::mem::take(&mut composed_areas)
+                    .into_iter()
+                    .partition(|area: &ComposedArea| area.layer_id == *layer_id);
+                composed_areas = rest;
+                in_layer
             })
             .collect();
     }
 
     fn compose(&mut self) {
         if let Some(mut strategy) = self.composition_strategy.take() {
-            strategy.compose(self);
+            let result = strategy.compose(self);
             self.composition_strategy = Some(strategy);
+
+            // Recovery policy: missing elements are recreated on the next
+            // frame and the rest of the composition is skipped for this one.
+            if let Err(err) = result {
+                if let HframeError::ElementNotFound(id) = &err {
+                    self.broken_html_ids.insert(id.clone());
+                }
+
+                self.handle_error(err);
+            }
         }
     }
 
     pub(crate) fn sync(&mut self) {
+        if self.auto_aware {
+            self.put_egui_areas();
+        }
+
         self.purge_composed_areas();
+        self.update_canvas_transform();
         self.sort_composed_areas();
         self.compose();
+        self.flush_messages();
+        self.flush_navigations();
+        self.receive_messages();
+        self.time_out_loads();
+    }
+
+    /// The backend used to access the DOM.
+    pub fn dom(&self) -> &dyn DomBackend {
+        &*self.dom
+    }
+
+    /// Maps egui coordinates to CSS pixels in the document.
+    pub fn canvas_transform(&self) -> &CanvasTransform {
+        &self.canvas_transform
+    }
+
+    /// Whether the HTML of the given area may receive pointer events.
+    ///
+    /// That's the case if the pointer is over the area's layer while not
+    /// dragging. When the pointer leaves the canvas (likely to enter the HTML
+    /// content), the previous state is kept.
+    pub(crate) fn is_html_interactive(&self, area_id: egui::Id, layer_id: egui::LayerId) -> bool {
+        let ctx = &self.egui_ctx;
+
+        if ctx.input(|i| i.pointer.button_down(egui::PointerButton::Primary)) {
+            return false;
+        }
+
+        match ctx.input(|i| i.pointer.hover_pos()) {
+            Some(pos) => ctx.layer_id_at(pos) == Some(layer_id),
+            None => self
+                .composed_areas
+                .iter()
+                .find(|area| area.id == area_id)
+                .and_then(|area| area.html.as_ref())
+                .is_some_and(|html| html.status.interactive),
+        }
     }
 
-    pub(crate) fn get_composed_areas(&self) -> &[ComposedArea] {
+    /// All known areas, sorted from back to front.
+    pub fn get_composed_areas(&self) -> &[ComposedArea] {
         &self.composed_areas
     }
 
-    /// Only safe to call in `compose` phase where the areas are known and
-    /// sorted.
-    pub(crate) fn get_composed_areas_on_top_of<'cmp>(
+    /// Only meaningful in the `compose` phase, where the areas are known and
+    /// sorted. Areas not known by this composition context have nothing on
+    /// top of them.
+    pub fn get_composed_areas_on_top_of<'cmp>(
         &'cmp self,
         of: &'cmp ComposedArea,
     ) -> impl Iterator<Item = &'cmp ComposedArea> + 'cmp {
-        let index = self
+        let start = self
             .composed_areas
             .iter()
             .position(|area| area.id == of.id)
-            .expect("The are is not known in this composition context");
+            .map_or(self.composed_areas.len(), |index| index + 1);
 
         // This will also consider the non-HTML area part which may not be
         // relevant for compositions, but makes this function generic.
-        self.composed_areas[index + 1..]
+        // Areas sharing a layer are painted together by egui, so they can't
+        // be on top of each other.
+        self.composed_areas[start..]
             .iter()
-            .filter(|area| area.rect.intersects(of.rect))
+            .filter(|area| area.layer_id != of.layer_id && self.hole_rect(area).intersects(of.rect))
     }
 
-    pub(crate) fn get_composed_area_being_dragged(&self) -> Option<&ComposedArea> {
+    /// Rect covered by the hole an area punches, including its shadow if
+    /// `set_shadow_holes` is enabled.
+    fn hole_rect(&self, area: &ComposedArea) -> egui::Rect {
+        match &area.shape {
+            AreaShape::Frame { shadow, .. } if self.shadow_holes => area.rect.expand(*shadow),
+            AreaShape::Frame { .. } => area.rect,
+            AreaShape::Polygon(points) => egui::Rect::from_points(points),
+        }
+    }
+
+    /// Shape of the hole an area punches, in CSS pixels relative to
+    /// `relative_to`.
+    pub(crate) fn hole_shape(
+        &self,
+        area: &ComposedArea,
+        relative_to: egui::Rect,
+    ) -> utils::geometry::Shape {
+        let transform = &self.canvas_transform;
+
+        match &area.shape {
+            AreaShape::Frame { rounding, .. } => {
+                let rect = self.hole_rect(area);
+                // How much the hole grows around the area, for shadows.
+                let margin = area.rect.left() - rect.left();
+                let radius = |r: f32| (r + margin) * transform.scale;
+                let rounding = egui::Rounding {
+                    nw: radius(rounding.nw),
+                    ne: radius(rounding.ne),
+                    sw: radius(rounding.sw),
+                    se: radius(rounding.se),
+                };
+
+                utils::geometry::Shape::RoundedRect(
+                    utils::geometry::rect_to_relative(transform.to_css_rect(rect), relative_to),
+                    rounding,
+                )
+            }
+            AreaShape::Polygon(points) => utils::geometry::Shape::Polygon(
+                points
+                    .iter()
+                    .map(|p| transform.to_css_pos(*p) - relative_to.min.to_vec2())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The area being dragged by the pointer, if any.
+    pub fn get_composed_area_being_dragged(&self) -> Option<&ComposedArea> {
         let egui_ctx = &self.egui_ctx;
         // Lazy detection of dragging.
         let dragging = egui_ctx.input(|i| i.pointer.button_down(egui::PointerButton::Primary));
@@ -169,23 +753,27 @@ impl CompositionContext {
             return None;
         }
 
-        let top_layer_id = egui_ctx.top_layer_id()?.id;
+        let top_layer_id = egui_ctx.top_layer_id()?;
         self.composed_areas
             .iter()
-            .find(|area| area.id == top_layer_id)
+            .find(|area| area.layer_id == top_layer_id)
     }
 }
 
 impl Drop for CompositionContext {
     fn drop(&mut self) {
-        let document = web_sys::window().unwrap().document().unwrap();
-        let style = document.get_element_by_id(GLOBAL_STYLES_ID);
-        if let Some(style) = style {
-            style.remove();
-        }
+        let _ = self.dom.remove_element(GLOBAL_STYLES_ID);
+        let _ = self.dom.remove_element(GLOBAL_SCRIPT_ID);
     }
 }
 
+/// Titles belong to the document being replaced.
+fn start_loading(html: &mut ComposedHtml, egui_ctx: &egui::Context) {
+    html.title = None;
+    html.load_state = Some(LoadState::Loading);
+    html.load_started = egui_ctx.input(|i| i.time);
+}
+
 #[derive(Clone)]
 pub(crate) struct WrappedCompositionContext(pub(crate) Arc<Mutex<CompositionContext>>);
 
@@ -214,10 +802,193 @@ pub(crate) fn get_composition_context(ctx: &egui::Context) -> WrappedComposition
     })
 }
 
+/// Replaces the strategy used to compose HTML content with egui areas.
+///
+/// By default, hframe picks a strategy based on the detected browser engine
+/// (see [`composition_strategies::for_browser_engine`]), this overrides it.
+///
+/// Can be called at startup, before the first `sync`, or at any point later to
+/// swap strategies at runtime.
+pub fn set_composition_strategy(ctx: &egui::Context, strategy: impl CompositionStrategy + 'static) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.set_composition_strategy(Box::new(strategy), "set explicitly");
+}
+
+/// Replaces the backend used to access the DOM.
+///
+/// This must be called before showing any HTML content, as elements created by
+/// the previous backend are not moved over. It's mostly useful for testing
+/// with [`dom_backends::Fake`].
+pub fn set_dom_backend(ctx: &egui::Context, dom: impl DomBackend + 'static) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.set_dom_backend(Box::new(dom));
+}
+
+/// Sets the id of the canvas egui renders to, so HTML content can be
+/// positioned relative to it.
+///
+/// By default, the first canvas found in the document is used.
+pub fn set_canvas_id(ctx: &egui::Context, canvas_id: &str) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.set_canvas_id(canvas_id);
+}
+
+/// Makes hframe aware of every egui area, including windows, popups, menus and
+/// tooltips, without calling `aware` on them.
+///
+/// Disabled by default.
+pub fn set_auto_aware(ctx: &egui::Context, auto_aware: bool) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.set_auto_aware(auto_aware);
+}
+
+/// Makes holes also cover the shadows of windows and popups, so they are not
+/// hidden by HTML content below them.
+///
+/// Disabled by default.
+pub fn set_shadow_holes(ctx: &egui::Context, shadow_holes: bool) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.set_shadow_holes(shadow_holes);
+}
+
+/// Sets how long iframes created with [`Iframe`](crate::Iframe) may take to
+/// load before their state becomes [`LoadState::Failed`].
+///
+/// 30 seconds by default.
+pub fn set_load_timeout(ctx: &egui::Context, load_timeout: Duration) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.set_load_timeout(load_timeout);
+}
+
+/// Removes the HTML element of a window kept alive with
+/// [`HtmlWindow::keep_alive`](crate::HtmlWindow::keep_alive), given its id.
+///
+/// Also works with shown windows, whose content is rendered again on the
+/// next frame.
+pub fn evict(ctx: &egui::Context, id: &str) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.evict(id);
+}
+
+/// Sends a value, serialized as JSON, to the HTML content with the given id
+/// (the id of an `HtmlWindow` or `Html` widget) without re-rendering it.
+///
+/// Messages are delivered on the next `sync`. If the content is an iframe, it
+/// receives the message through `postMessage`, as long as its document has the
+/// origin of its `src` (or the page origin for `srcdoc` iframes). Messages
+/// posted before the iframe loads are lost, wait for
+/// [`LoadState::Loaded`](crate::LoadState::Loaded) in the window response
+/// first. Other content receives a `hframe-message` `CustomEvent` holding the
+/// message as `detail`, dispatched on the element with the given id:
+///
+/// ```js
+/// document.getElementById("counter").addEventListener("hframe-message", (event) => {
+///     console.log(event.detail);
+/// });
+/// ```
+pub fn post_message(
+    ctx: &egui::Context,
+    id: &str,
+    value: &impl serde::Serialize,
+) -> Result<(), HframeError> {
+    let json =
+        serde_json::to_string(value).map_err(|err| HframeError::Serialization(err.to_string()))?;
+
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.post_message(id, json);
+    Ok(())
+}
+
+/// Navigates the iframe making up the HTML content with the given id (the id
+/// of an `HtmlWindow` or `Html` widget) on the next `sync`.
+///
+/// Going back and forward is only possible for same-origin content, as the
+/// history of other iframes can't be accessed. Those are reloaded by setting
+/// their `src` again.
+pub fn navigate(ctx: &egui::Context, id: &str, navigation: Navigation) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.navigate(id, navigation);
+}
+
+/// Takes the messages sent during the last frame by the HTML content with the
+/// given id (the id of an `HtmlWindow` or `Html` widget), deserialized from
+/// JSON. Messages not taken are dropped on the next `sync`.
+///
+/// Content in the page sends messages with the injected `hframe.send` helper,
+/// passing its id or any element inside it:
+///
+/// ```html
+/// <button onclick="hframe.send(this, { clicked: true })">Click me</button>
+/// ```
+///
+/// Iframes use `window.parent.postMessage` instead, but only messages from the
+/// page origin are accepted unless allowed with
+/// [`HtmlWindow::allowed_origin`](crate::HtmlWindow::allowed_origin) or
+/// [`Html::allowed_origin`](crate::Html::allowed_origin).
+///
+/// Messages that can't be deserialized are reported as errors.
+pub fn take_messages<T: serde::de::DeserializeOwned>(ctx: &egui::Context, id: &str) -> Vec<T> {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.take_messages(id)
+}
+
+/// Takes the DOM events (clicks, inputs, changes, submits, focus and blur)
+/// happened during the last frame inside the HTML content with the given id
+/// (the id of an `HtmlWindow` or `Html` widget). Events not taken are dropped
+/// on the next `sync`.
+///
+/// Events inside iframes can't be observed, use messages instead.
+pub fn take_events(ctx: &egui::Context, id: &str) -> Vec<HtmlEvent> {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.take_events(id)
+}
+
+/// Sets a callback receiving the errors hframe recovered from, like HTML
+/// elements removed by third-party scripts.
+///
+/// Errors are passed at the end of `sync`, once hframe is unlocked, so the
+/// callback may call hframe functions like `evict`. Without a callback, errors
+/// are only logged.
+pub fn set_error_callback(ctx: &egui::Context, callback: impl FnMut(HframeError) + Send + 'static) {
+    let cmp = get_composition_context(ctx);
+    let mut cmp = cmp.lock().unwrap();
+    cmp.set_error_callback(Box::new(callback));
+}
+
 /// Syncs hframe internal stuff between the egui and web worlds. This function
 /// **must be always called** at the end of the update loop unconditionally.
 pub fn sync(ctx: &egui::Context) {
     let cmp = get_composition_context(ctx);
-    let mut cmp = cmp.lock().unwrap();
-    cmp.sync();
+    let pending_errors = {
+        let mut cmp = cmp.lock().unwrap();
+        cmp.sync();
+        cmp.take_pending_errors()
+    };
+
+    let Some((callback, errors)) = pending_errors else {
+        return;
+    };
+
+    // Busy if `sync` is called from the callback itself. The errors are then
+    // passed on the next one.
+    let Ok(mut callback) = callback.try_lock() else {
+        let mut cmp = cmp.lock().unwrap();
+        cmp.pending_errors.splice(0..0, errors);
+        return;
+    };
+
+    for err in errors {
+        callback(err);
+    }
 }
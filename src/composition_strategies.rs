@@ -1,3 +1,22 @@
+//! Built-in implementations of [`crate::CompositionStrategy`].
+
+use crate::{BrowserEngine, CompositionStrategy};
+
+mod clip_path;
 mod svg_data_mask;
 
-pub(crate) use svg_data_mask::*;
+pub use clip_path::*;
+pub use svg_data_mask::*;
+
+/// Picks the built-in strategy performing best on the given browser engine.
+///
+/// This is what hframe uses by default. Call [`crate::set_composition_strategy`]
+/// to override it.
+pub fn for_browser_engine(engine: BrowserEngine) -> Box<dyn CompositionStrategy> {
+    match engine {
+        // Blink updates data masks fast enough to follow dragged windows.
+        BrowserEngine::Blink | BrowserEngine::Unknown => Box::new(SvgDataMask::new()),
+        // Gecko and WebKit lag behind when masks change, clip paths don't.
+        BrowserEngine::Gecko | BrowserEngine::WebKit => Box::new(ClipPath::new()),
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    utils::{self, geometry::Shape},
+    CompositionContext, CompositionStrategy, HframeError,
+};
+
+/// Composes HTML areas by clipping them with an even-odd `clip-path` built
+/// from the outer rect of the area and the shapes of the areas on top of it.
+///
+/// Unlike masks, clip paths are cheap to update, so no tricks are needed while
+/// dragging on any browser.
+#[derive(Default)]
+pub struct ClipPath;
+
+impl ClipPath {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CompositionStrategy for ClipPath {
+    fn name(&self) -> &'static str {
+        "clip_path"
+    }
+
+    fn compose(&mut self, cmp: &mut CompositionContext) -> Result<(), HframeError> {
+        for area in cmp.get_composed_areas() {
+            if area.html.is_none() {
+                continue;
+            }
+
+            let area_html = area.html.as_ref().unwrap();
+
+            let transform = cmp.canvas_transform();
+            let area_rect = transform.to_css_rect(area_html.rect);
+
+            let visible_rect = utils::geometry::rect_to_relative(
+                transform.to_css_rect(area_html.visible_rect()),
+                area_rect,
+            );
+            let hole_shapes: Vec<_> = cmp
+                .get_composed_areas_on_top_of(area)
+                .filter_map(|hole| cmp.hole_shape(hole, area_rect).intersect(visible_rect))
+                .collect();
+
+            let clip_path = compute_clip_path(visible_rect, hole_shapes);
+
+            cmp.dom()
+                .set_style_property(&area_html.id, "clip-path", &clip_path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Holes can overlap each other, which would make them visible again with the
+/// even-odd rule. Overlapping holes are split into disjoint rects, losing their
+/// exact shape, while the rest keep it.
+///
+/// `visible_rect` and `hole_shapes` must be relative to the HTML element, with
+/// the holes already clipped to `visible_rect`.
+fn compute_clip_path(visible_rect: egui::Rect, hole_shapes: Vec<Shape>) -> String {
+    let mut groups: Vec<Vec<Shape>> = Vec::new();
+    for hole in hole_shapes {
+        let rect = hole.bounding_rect();
+        let (mut overlapping, rest): (Vec<_>, Vec<_>) = groups.into_iter().partition(|group| {
+            group
+                .iter()
+                .any(|shape| shape.bounding_rect().intersects(rect))
+        });
+
+        let mut group: Vec<_> = overlapping.drain(..).flatten().collect();
+        group.push(hole);
+        groups = rest;
+        groups.push(group);
+    }
+
+    let holes = groups.into_iter().flat_map(|mut group| {
+        if group.len() == 1 {
+            group.pop().into_iter().collect()
+        } else {
+            let rects: Vec<_> = group.iter().map(Shape::bounding_rect).collect();
+            utils::geometry::disjoint_union(&rects)
+                .into_iter()
+                .map(|rect| Shape::RoundedRect(rect, egui::Rounding::ZERO))
+                .collect::<Vec<_>>()
+        }
+    });
+
+    let path = std::iter::once(Shape::RoundedRect(visible_rect, egui::Rounding::ZERO))
+        .chain(holes)
+        .map(|shape| shape.to_path_data())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!("path(evenodd, \"{path}\")")
+}
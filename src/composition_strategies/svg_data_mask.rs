@@ -1,12 +1,11 @@
-use crate::{utils, CompositionContext, CompositionStrategy};
+use crate::{utils, BrowserEngine, CompositionContext, CompositionStrategy, HframeError};
 use std::collections::{HashMap, HashSet};
-use web_sys::wasm_bindgen::JsCast;
 
 const MASK_TEMPLATE: &str = r#"
 <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}">
   <defs>
     <mask id="mask" x="0" y="0" width="{width}" height="{height}">
-      <rect x="0" y="0" width="{width}" height="{height}" fill="white" />
+      <rect x="{visible_x}" y="{visible_y}" width="{visible_width}" height="{visible_height}" fill="white" />
       {holes}      
     </mask>
   </defs>
@@ -14,21 +13,26 @@ const MASK_TEMPLATE: &str = r#"
 </svg>
 "#;
 
-const HOLE_TEMPLATE: &str =
-    r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" rx="5" fill="black" />"#;
+const HOLE_TEMPLATE: &str = r#"<path d="{path}" fill="black" />"#;
 
-pub(crate) struct SvgDataMask {
+/// Composes HTML areas by applying a URL-encoded SVG image as `mask`, with a
+/// hole shaped like every area on top of it.
+///
+/// On non-Blink browsers, HTML behind an area being dragged is hidden while the
+/// mask changes, since updating it is too slow there.
+pub struct SvgDataMask {
     // Reading the previous mask directly from the element is not safe since hframe
     // can clean styles between cycles. That's why we must keep track of the previous
     // values manually.
     previous_masks: HashMap<egui::Id, String>,
+    engine: BrowserEngine,
 }
 
 impl SvgDataMask {
-    #[allow(clippy::new_without_default)]
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             previous_masks: HashMap::new(),
+            engine: BrowserEngine::detect(),
         }
     }
 
@@ -38,12 +42,18 @@ impl SvgDataMask {
     }
 }
 
+impl Default for SvgDataMask {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CompositionStrategy for SvgDataMask {
     fn name(&self) -> &'static str {
         "svg_data_mask"
     }
 
-    fn compose(&mut self, cmp: &mut CompositionContext) {
+    fn compose(&mut self, cmp: &mut CompositionContext) -> Result<(), HframeError> {
         // Clean tracking garbage to avoid memory leaks.
         self.purge_previous_masks(cmp);
         for area in cmp.get_composed_areas() {
@@ -53,71 +63,69 @@ impl CompositionStrategy for SvgDataMask {
 
             let area_html = area.html.as_ref().unwrap();
 
-            let area_rect = area_html.rect;
+            let transform = cmp.canvas_transform();
+            let area_rect = transform.to_css_rect(area_html.rect);
 
             let holes: Vec<_> = cmp.get_composed_areas_on_top_of(area).collect();
 
-            let hole_rects: Vec<_> = holes
+            let hole_shapes: Vec<_> = holes
                 .iter()
-                .map(|hole| utils::geometry::rect_to_relative(hole.rect, area_rect))
+                .map(|hole| cmp.hole_shape(hole, area_rect))
                 .collect();
-            let area_rect = utils::geometry::rect_to_relative(area_rect, area_rect);
+            let visible_rect = utils::geometry::rect_to_relative(
+                transform.to_css_rect(area_html.visible_rect()),
+                area_rect,
+            );
 
-            let mask = compute_mask(area_rect, &hole_rects);
-
-            let document = web_sys::window().unwrap().document().unwrap();
-
-            let element = document
-                .get_element_by_id(&area.html.as_ref().unwrap().id)
-                .expect("Element to compose not found")
-                .dyn_into::<web_sys::HtmlElement>()
-                .unwrap();
+            let mask = compute_mask(area_rect, visible_rect, &hole_shapes);
 
             let prev_mask = self.previous_masks.get(&area.id);
 
             let area_being_dragged = cmp.get_composed_area_being_dragged();
             let is_hole_being_dragged =
-                area_being_dragged.map_or(false, |a| holes.iter().any(|h| h.id == a.id));
+                area_being_dragged.is_some_and(|a| holes.iter().any(|h| h.id == a.id));
 
             if is_hole_being_dragged
-                && !utils::browser_detection::is_blink()
+                && self.engine != BrowserEngine::Blink
                 && prev_mask != Some(&mask)
             {
-                area_html
-                    .get_html_element()
-                    .style()
-                    .set_property("visibility", "hidden")
-                    .unwrap();
+                cmp.dom()
+                    .set_style_property(&area_html.id, "visibility", "hidden")?;
 
                 // Hack: Destroy the previous mask so it can't match again until
                 // drag stops. This is to prevent the hidden element from appearing
                 // if you move the dragged area to it's original position.
-                *self.previous_masks.get_mut(&area.id).unwrap() = "".into();
+                self.previous_masks.insert(area.id, "".into());
             } else {
-                let style = element.style();
-                style.set_property("mask", &mask).unwrap();
-                style.set_property("-webkit-mask", &mask).unwrap();
+                let dom = cmp.dom();
+                dom.set_style_property(&area_html.id, "mask", &mask)?;
+                dom.set_style_property(&area_html.id, "-webkit-mask", &mask)?;
                 self.previous_masks.insert(area.id, mask);
             }
         }
+
+        Ok(())
     }
 }
 
-fn compute_mask(area_rect: egui::Rect, hole_rects: &[egui::Rect]) -> String {
-    let holes = hole_rects
+/// `visible_rect` and `hole_shapes` must be relative to `area_rect`.
+fn compute_mask(
+    area_rect: egui::Rect,
+    visible_rect: egui::Rect,
+    hole_shapes: &[utils::geometry::Shape],
+) -> String {
+    let holes = hole_shapes
         .iter()
-        .map(|hole| {
-            HOLE_TEMPLATE
-                .replace("{x}", &hole.min.x.to_string())
-                .replace("{y}", &hole.min.y.to_string())
-                .replace("{width}", &hole.width().to_string())
-                .replace("{height}", &hole.height().to_string())
-        })
+        .map(|hole| HOLE_TEMPLATE.replace("{path}", &hole.to_path_data()))
         .collect::<String>();
 
     let svg = MASK_TEMPLATE
         .replace("{width}", &area_rect.width().to_string())
         .replace("{height}", &area_rect.height().to_string())
+        .replace("{visible_x}", &visible_rect.min.x.to_string())
+        .replace("{visible_y}", &visible_rect.min.y.to_string())
+        .replace("{visible_width}", &visible_rect.width().to_string())
+        .replace("{visible_height}", &visible_rect.height().to_string())
         .replace("{holes}", &holes);
 
     format!("url(data:image/svg+xml,{})", urlencoding::encode(&svg))
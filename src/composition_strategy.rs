@@ -1,6 +1,15 @@
-use crate::CompositionContext;
+use crate::{CompositionContext, HframeError};
 
-pub(crate) trait CompositionStrategy: Send {
+/// Technique used to make egui areas on top of HTML content visible.
+///
+/// Built-in strategies live in [`crate::composition_strategies`]. You can also
+/// implement your own and install it with [`crate::set_composition_strategy`].
+pub trait CompositionStrategy: Send {
+    /// Name used to identify the strategy in logs.
     fn name(&self) -> &'static str;
-    fn compose(&mut self, cmp: &mut CompositionContext);
+    /// Called on every `sync`, after the composed areas are known and sorted
+    /// from back to front.
+    ///
+    /// Returning an error skips the rest of the composition for this frame.
+    fn compose(&mut self, cmp: &mut CompositionContext) -> Result<(), HframeError>;
 }
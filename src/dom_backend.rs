@@ -0,0 +1,77 @@
+use crate::{HframeError, Navigation};
+
+/// A message sent by HTML content to the egui app, or a DOM event happening
+/// inside it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtmlMessage {
+    /// Id of the HTML element holding the content that sent the message.
+    pub id: String,
+    /// Origin of the iframe that sent the message. `None` if it comes from
+    /// the same origin as the page.
+    pub origin: Option<String>,
+    pub json: String,
+}
+
+/// Status of HTML content reported by the page, only used by hframe itself,
+/// unlike [`HtmlEvent`](crate::HtmlEvent)s.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum HtmlStatus {
+    /// An iframe loaded a document, which may be an error page.
+    Load,
+    /// An iframe fired an `error` event.
+    Error,
+    /// The title of the document of a same-origin iframe changed.
+    Title { title: String },
+}
+
+/// Every DOM access performed by hframe goes through this trait, so the rest
+/// of the crate doesn't depend on running inside a browser.
+///
+/// Elements are referenced by their id. Operations on a missing element must
+/// fail with [`HframeError::ElementNotFound`] so hframe can recreate it.
+pub trait DomBackend: Send {
+    /// Appends a `<style>` element with the given id and CSS to the document
+    /// head, unless it already exists.
+    fn insert_style(&self, id: &str, css: &str) -> Result<(), HframeError>;
+    /// Appends a `<script>` element with the given id and JavaScript to the
+    /// document head, unless it already exists.
+    fn insert_script(&self, id: &str, js: &str) -> Result<(), HframeError>;
+    /// Replaces the element with the given id by the given outer HTML. If the
+    /// element doesn't exist, it is created at the end of the body.
+    fn replace_element(&self, id: &str, outer_html: &str) -> Result<(), HframeError>;
+    /// Updates the element with the given id to match the given outer HTML,
+    /// only touching the nodes and attributes that changed. The rest are kept
+    /// alive with their state, like focus, input values or loaded iframes. If
+    /// the element doesn't exist, it is created like with `replace_element`.
+    fn patch_element(&self, id: &str, outer_html: &str) -> Result<(), HframeError>;
+    fn set_attribute(&self, id: &str, name: &str, value: &str) -> Result<(), HframeError>;
+    fn set_style_property(&self, id: &str, property: &str, value: &str) -> Result<(), HframeError>;
+    /// Delivers a JSON message to the content of the element with the given
+    /// id. Iframes receive it through `postMessage`, only if their document
+    /// still has the origin of their `src`. Anything else receives it as a
+    /// `hframe-message` `CustomEvent` dispatched on the element.
+    fn post_message(&self, id: &str, json: &str) -> Result<(), HframeError>;
+    /// Navigates the iframe inside the element with the given id. Does
+    /// nothing if there is no iframe.
+    ///
+    /// Returns whether the iframe started loading a document.
+    fn navigate(&self, id: &str, navigation: Navigation) -> Result<bool, HframeError>;
+    /// Messages sent by HTML content since the last call.
+    fn take_messages(&self) -> Result<Vec<HtmlMessage>, HframeError>;
+    /// DOM events happened inside HTML content since the last call, as
+    /// messages holding an [`HtmlEvent`](crate::HtmlEvent) as JSON.
+    fn take_events(&self) -> Result<Vec<HtmlMessage>, HframeError>;
+    /// Status updates of HTML content since the last call, as messages
+    /// holding an [`HtmlStatus`] as JSON.
+    fn take_status(&self) -> Result<Vec<HtmlMessage>, HframeError>;
+    /// Removes the element with the given id if it exists.
+    fn remove_element(&self, id: &str) -> Result<(), HframeError>;
+    /// Rect of the canvas egui renders to, relative to the document, in CSS
+    /// pixels. Uses the first canvas in the document if no id is given.
+    ///
+    /// `None` if the canvas doesn't exist.
+    fn canvas_rect(&self, canvas_id: Option<&str>) -> Result<Option<egui::Rect>, HframeError>;
+    /// Logs a debug message with a label and a value.
+    fn debug(&self, label: &str, value: &str);
+}
@@ -0,0 +1,22 @@
+//! Built-in implementations of [`crate::DomBackend`].
+
+mod fake;
+mod noop;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+pub use fake::*;
+pub use noop::*;
+#[cfg(target_arch = "wasm32")]
+pub use web::*;
+
+use crate::DomBackend;
+
+/// The backend used by default on the current target.
+pub(crate) fn default_backend() -> Box<dyn DomBackend> {
+    #[cfg(target_arch = "wasm32")]
+    return Box::new(Web);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    return Box::new(Noop);
+}
@@ -0,0 +1,263 @@
+use crate::{DomBackend, HframeError, HtmlMessage, HtmlStatus, Navigation};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+/// A DOM operation recorded by [`Fake`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DomOperation {
+    InsertStyle {
+        id: String,
+    },
+    InsertScript {
+        id: String,
+    },
+    CreateElement {
+        id: String,
+    },
+    ReplaceElement {
+        id: String,
+        outer_html: String,
+    },
+    PatchElement {
+        id: String,
+        outer_html: String,
+    },
+    SetAttribute {
+        id: String,
+        name: String,
+        value: String,
+    },
+    SetStyleProperty {
+        id: String,
+        property: String,
+        value: String,
+    },
+    PostMessage {
+        id: String,
+        json: String,
+    },
+    Navigate {
+        id: String,
+        navigation: Navigation,
+    },
+    RemoveElement {
+        id: String,
+    },
+}
+
+#[derive(Default)]
+struct FakeState {
+    element_ids: HashSet<String>,
+    canvas_rect: Option<egui::Rect>,
+    operations: Vec<DomOperation>,
+    messages: Vec<HtmlMessage>,
+    events: Vec<HtmlMessage>,
+    status: Vec<HtmlMessage>,
+}
+
+/// In-memory backend recording every operation, useful for testing.
+///
+/// Clones share the same state, so you can keep one to inspect the recorded
+/// operations after handing another to [`crate::set_dom_backend`].
+#[derive(Clone, Default)]
+pub struct Fake {
+    state: Arc<Mutex<FakeState>>,
+}
+
+impl Fake {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All operations recorded so far.
+    pub fn operations(&self) -> Vec<DomOperation> {
+        self.state.lock().unwrap().operations.clone()
+    }
+
+    /// Returns the operations recorded so far and forgets about them.
+    pub fn take_operations(&self) -> Vec<DomOperation> {
+        std::mem::take(&mut self.state.lock().unwrap().operations)
+    }
+
+    /// Whether an element with the given id currently exists.
+    pub fn has_element(&self, id: &str) -> bool {
+        self.state.lock().unwrap().element_ids.contains(id)
+    }
+
+    /// Removes an element without recording it, like a third-party script
+    /// would do.
+    pub fn detach_element(&self, id: &str) {
+        self.state.lock().unwrap().element_ids.remove(id);
+    }
+
+    /// Sets the rect reported for the canvas. There is no canvas by default.
+    pub fn set_canvas_rect(&self, rect: Option<egui::Rect>) {
+        self.state.lock().unwrap().canvas_rect = rect;
+    }
+
+    /// Simulates HTML content sending a message.
+    pub fn send_message(&self, message: HtmlMessage) {
+        self.state.lock().unwrap().messages.push(message);
+    }
+
+    /// Simulates a DOM event happening inside HTML content.
+    pub fn dispatch_event(&self, id: &str, event: &crate::HtmlEvent) {
+        self.state.lock().unwrap().events.push(HtmlMessage {
+            id: id.to_string(),
+            origin: None,
+            json: serde_json::to_string(event).unwrap(),
+        });
+    }
+
+    /// Simulates HTML content reporting its status, like an iframe loading.
+    pub fn report_status(&self, id: &str, status: &HtmlStatus) {
+        self.state.lock().unwrap().status.push(HtmlMessage {
+            id: id.to_string(),
+            origin: None,
+            json: serde_json::to_string(status).unwrap(),
+        });
+    }
+
+    fn ensure_element(&self, id: &str) -> Result<(), HframeError> {
+        if self.has_element(id) {
+            Ok(())
+        } else {
+            Err(HframeError::ElementNotFound(id.to_string()))
+        }
+    }
+
+    fn create_element(&self, id: &str) {
+        let created = self
+            .state
+            .lock()
+            .unwrap()
+            .element_ids
+            .insert(id.to_string());
+
+        if created {
+            self.record(DomOperation::CreateElement { id: id.to_string() });
+        }
+    }
+
+    fn record(&self, operation: DomOperation) {
+        self.state.lock().unwrap().operations.push(operation);
+    }
+}
+
+impl DomBackend for Fake {
+    fn insert_style(&self, id: &str, _css: &str) -> Result<(), HframeError> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.element_ids.insert(id.to_string()) {
+            state
+                .operations
+                .push(DomOperation::InsertStyle { id: id.to_string() });
+        }
+
+        Ok(())
+    }
+
+    fn insert_script(&self, id: &str, _js: &str) -> Result<(), HframeError> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.element_ids.insert(id.to_string()) {
+            state
+                .operations
+                .push(DomOperation::InsertScript { id: id.to_string() });
+        }
+
+        Ok(())
+    }
+
+    fn replace_element(&self, id: &str, outer_html: &str) -> Result<(), HframeError> {
+        self.create_element(id);
+        self.record(DomOperation::ReplaceElement {
+            id: id.to_string(),
+            outer_html: outer_html.to_string(),
+        });
+
+        Ok(())
+    }
+
+    fn patch_element(&self, id: &str, outer_html: &str) -> Result<(), HframeError> {
+        self.create_element(id);
+        self.record(DomOperation::PatchElement {
+            id: id.to_string(),
+            outer_html: outer_html.to_string(),
+        });
+
+        Ok(())
+    }
+
+    fn set_attribute(&self, id: &str, name: &str, value: &str) -> Result<(), HframeError> {
+        self.ensure_element(id)?;
+        self.record(DomOperation::SetAttribute {
+            id: id.to_string(),
+            name: name.to_string(),
+            value: value.to_string(),
+        });
+
+        Ok(())
+    }
+
+    fn set_style_property(&self, id: &str, property: &str, value: &str) -> Result<(), HframeError> {
+        self.ensure_element(id)?;
+        self.record(DomOperation::SetStyleProperty {
+            id: id.to_string(),
+            property: property.to_string(),
+            value: value.to_string(),
+        });
+
+        Ok(())
+    }
+
+    fn post_message(&self, id: &str, json: &str) -> Result<(), HframeError> {
+        self.ensure_element(id)?;
+        self.record(DomOperation::PostMessage {
+            id: id.to_string(),
+            json: json.to_string(),
+        });
+
+        Ok(())
+    }
+
+    fn navigate(&self, id: &str, navigation: Navigation) -> Result<bool, HframeError> {
+        self.ensure_element(id)?;
+        self.record(DomOperation::Navigate {
+            id: id.to_string(),
+            navigation,
+        });
+
+        Ok(navigation != Navigation::OpenInNewTab)
+    }
+
+    fn take_messages(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        Ok(std::mem::take(&mut self.state.lock().unwrap().messages))
+    }
+
+    fn take_events(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        Ok(std::mem::take(&mut self.state.lock().unwrap().events))
+    }
+
+    fn take_status(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        Ok(std::mem::take(&mut self.state.lock().unwrap().status))
+    }
+
+    fn remove_element(&self, id: &str) -> Result<(), HframeError> {
+        let removed = self.state.lock().unwrap().element_ids.remove(id);
+
+        if removed {
+            self.record(DomOperation::RemoveElement { id: id.to_string() });
+        }
+
+        Ok(())
+    }
+
+    fn canvas_rect(&self, _canvas_id: Option<&str>) -> Result<Option<egui::Rect>, HframeError> {
+        Ok(self.state.lock().unwrap().canvas_rect)
+    }
+
+    fn debug(&self, _label: &str, _value: &str) {}
+}
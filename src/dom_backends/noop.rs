@@ -0,0 +1,68 @@
+use crate::{DomBackend, HframeError, HtmlMessage, Navigation};
+
+/// Backend that ignores every operation.
+///
+/// Used by default outside of WebAssembly so applications sharing code with
+/// the web can still show HTML windows without crashing.
+pub struct Noop;
+
+impl DomBackend for Noop {
+    fn insert_style(&self, _id: &str, _css: &str) -> Result<(), HframeError> {
+        Ok(())
+    }
+
+    fn insert_script(&self, _id: &str, _js: &str) -> Result<(), HframeError> {
+        Ok(())
+    }
+
+    fn replace_element(&self, _id: &str, _outer_html: &str) -> Result<(), HframeError> {
+        Ok(())
+    }
+
+    fn patch_element(&self, _id: &str, _outer_html: &str) -> Result<(), HframeError> {
+        Ok(())
+    }
+
+    fn set_attribute(&self, _id: &str, _name: &str, _value: &str) -> Result<(), HframeError> {
+        Ok(())
+    }
+
+    fn set_style_property(
+        &self,
+        _id: &str,
+        _property: &str,
+        _value: &str,
+    ) -> Result<(), HframeError> {
+        Ok(())
+    }
+
+    fn post_message(&self, _id: &str, _json: &str) -> Result<(), HframeError> {
+        Ok(())
+    }
+
+    fn navigate(&self, _id: &str, _navigation: Navigation) -> Result<bool, HframeError> {
+        Ok(false)
+    }
+
+    fn take_messages(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        Ok(Vec::new())
+    }
+
+    fn take_events(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        Ok(Vec::new())
+    }
+
+    fn take_status(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        Ok(Vec::new())
+    }
+
+    fn remove_element(&self, _id: &str) -> Result<(), HframeError> {
+        Ok(())
+    }
+
+    fn canvas_rect(&self, _canvas_id: Option<&str>) -> Result<Option<egui::Rect>, HframeError> {
+        Ok(None)
+    }
+
+    fn debug(&self, _label: &str, _value: &str) {}
+}
@@ -0,0 +1,292 @@
+mod morph;
+
+use crate::{DomBackend, HframeError, HtmlMessage, Navigation};
+use web_sys::{
+    js_sys,
+    wasm_bindgen::{JsCast, JsValue},
+};
+
+/// Backend operating on the browser's document.
+pub struct Web;
+
+impl Web {
+    fn window(&self) -> Result<web_sys::Window, HframeError> {
+        web_sys::window().ok_or(HframeError::NoWindow)
+    }
+
+    fn document(&self) -> Result<web_sys::Document, HframeError> {
+        self.window()?.document().ok_or(HframeError::NoDocument)
+    }
+
+    fn html_element(&self, id: &str) -> Result<web_sys::HtmlElement, HframeError> {
+        self.document()?
+            .get_element_by_id(id)
+            .ok_or_else(|| HframeError::ElementNotFound(id.to_string()))?
+            .dyn_into::<web_sys::HtmlElement>()
+            .map_err(|_| HframeError::ElementNotFound(id.to_string()))
+    }
+}
+
+fn js_error(err: JsValue) -> HframeError {
+    HframeError::Js(err.as_string().unwrap_or_else(|| format!("{err:?}")))
+}
+
+/// Origin the document of an iframe must have to receive messages, so they
+/// don't leak to whatever it navigated or got redirected to. Iframes without
+/// a URL origin, like `srcdoc` ones, are expected to share the page one.
+fn target_origin(iframe: &web_sys::HtmlIFrameElement) -> String {
+    let origin = web_sys::Url::new(&iframe.src())
+        .ok()
+        .filter(|_| !iframe.has_attribute("srcdoc"))
+        .map(|url| url.origin())
+        .filter(|origin| origin != "null");
+
+    // `/` stands for the origin of the page.
+    origin.unwrap_or_else(|| "/".to_string())
+}
+
+/// The iframe making up the content of an hframe element, if any.
+fn child_iframe(
+    element: &web_sys::HtmlElement,
+) -> Result<Option<web_sys::HtmlIFrameElement>, HframeError> {
+    Ok(element
+        .query_selector(":scope > iframe")
+        .map_err(js_error)?
+        .and_then(|iframe| iframe.dyn_into::<web_sys::HtmlIFrameElement>().ok()))
+}
+
+impl Web {
+    /// Calls one of the functions set by `hframe.js` returning queued
+    /// messages.
+    fn take_from_script(&self, function: &str) -> Result<Vec<HtmlMessage>, HframeError> {
+        let window = JsValue::from(self.window()?);
+        let hframe = js_sys::Reflect::get(&window, &"hframe".into()).map_err(js_error)?;
+        if hframe.is_undefined() {
+            return Ok(Vec::new());
+        }
+
+        let take = js_sys::Reflect::get(&hframe, &function.into())
+            .map_err(js_error)?
+            .dyn_into::<js_sys::Function>()
+            .map_err(js_error)?;
+        let messages = take
+            .call0(&hframe)
+            .map_err(js_error)?
+            .dyn_into::<js_sys::Array>()
+            .map_err(js_error)?;
+
+        // Each message is an `[id, origin, json]` array.
+        Ok(messages
+            .iter()
+            .filter_map(|message| {
+                let message = message.dyn_into::<js_sys::Array>().ok()?;
+                Some(HtmlMessage {
+                    id: message.get(0).as_string()?,
+                    origin: message.get(1).as_string(),
+                    json: message.get(2).as_string()?,
+                })
+            })
+            .collect())
+    }
+}
+
+impl DomBackend for Web {
+    fn insert_style(&self, id: &str, css: &str) -> Result<(), HframeError> {
+        let document = self.document()?;
+
+        if document.get_element_by_id(id).is_none() {
+            document
+                .head()
+                .ok_or(HframeError::NoHead)?
+                .insert_adjacent_html("beforeend", &format!("<style id=\"{id}\">{css}</style>"))
+                .map_err(js_error)?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_script(&self, id: &str, js: &str) -> Result<(), HframeError> {
+        let document = self.document()?;
+
+        // Scripts inserted as HTML don't run, so the element is built by hand.
+        if document.get_element_by_id(id).is_none() {
+            let script = document.create_element("script").map_err(js_error)?;
+            script.set_id(id);
+            script.set_text_content(Some(js));
+            document
+                .head()
+                .ok_or(HframeError::NoHead)?
+                .append_child(&script)
+                .map_err(js_error)?;
+        }
+
+        Ok(())
+    }
+
+    fn replace_element(&self, id: &str, outer_html: &str) -> Result<(), HframeError> {
+        let document = self.document()?;
+
+        let element = match document.get_element_by_id(id) {
+            Some(element) => element,
+            None => {
+                let body = document.body().ok_or(HframeError::NoBody)?;
+                let element = document.create_element("div").map_err(js_error)?;
+                body.append_child(&element).map_err(js_error)?;
+                element
+            }
+        };
+
+        element.set_outer_html(outer_html);
+        Ok(())
+    }
+
+    fn patch_element(&self, id: &str, outer_html: &str) -> Result<(), HframeError> {
+        let document = self.document()?;
+
+        let Some(element) = document.get_element_by_id(id) else {
+            return self.replace_element(id, outer_html);
+        };
+
+        // Templates parse HTML without running scripts or loading resources.
+        let template = document
+            .create_element("template")
+            .map_err(js_error)?
+            .unchecked_into::<web_sys::HtmlTemplateElement>();
+        template.set_inner_html(outer_html);
+
+        match template.content().first_element_child() {
+            Some(new_element) => morph::morph(&element, &new_element).map_err(js_error),
+            None => self.replace_element(id, outer_html),
+        }
+    }
+
+    fn set_attribute(&self, id: &str, name: &str, value: &str) -> Result<(), HframeError> {
+        self.html_element(id)?
+            .set_attribute(name, value)
+            .map_err(js_error)
+    }
+
+    fn set_style_property(&self, id: &str, property: &str, value: &str) -> Result<(), HframeError> {
+        self.html_element(id)?
+            .style()
+            .set_property(property, value)
+            .map_err(js_error)
+    }
+
+    fn post_message(&self, id: &str, json: &str) -> Result<(), HframeError> {
+        let element = self.html_element(id)?;
+        let message = js_sys::JSON::parse(json).map_err(js_error)?;
+
+        if let Some(iframe) = child_iframe(&element)? {
+            // The content is not loaded yet if there is no window.
+            if let Some(content_window) = iframe.content_window() {
+                content_window
+                    .post_message(&message, &target_origin(&iframe))
+                    .map_err(js_error)?;
+            }
+
+            return Ok(());
+        }
+
+        let mut init = web_sys::CustomEventInit::new();
+        init.detail(&message);
+        let event = web_sys::CustomEvent::new_with_event_init_dict("hframe-message", &init)
+            .map_err(js_error)?;
+        element.dispatch_event(&event).map_err(js_error)?;
+
+        Ok(())
+    }
+
+    fn navigate(&self, id: &str, navigation: Navigation) -> Result<bool, HframeError> {
+        let element = self.html_element(id)?;
+        let Some(iframe) = child_iframe(&element)? else {
+            return Ok(false);
+        };
+
+        // Accessing the history or location of a cross-origin window throws.
+        let content_window = iframe.content_window();
+        let history = content_window.as_ref().and_then(|w| w.history().ok());
+        let location = content_window.as_ref().map(|w| w.location());
+        let href = location.as_ref().and_then(|l| l.href().ok());
+
+        let loading = match navigation {
+            Navigation::Back => history
+                .map(|h| h.back())
+                .transpose()
+                .map_err(js_error)?
+                .is_some(),
+            Navigation::Forward => history
+                .map(|h| h.forward())
+                .transpose()
+                .map_err(js_error)?
+                .is_some(),
+            Navigation::Reload => {
+                match location.filter(|_| href.is_some()) {
+                    Some(location) => location.reload().map_err(js_error)?,
+                    None => iframe.set_src(&iframe.src()),
+                }
+                true
+            }
+            Navigation::OpenInNewTab => {
+                self.window()?
+                    .open_with_url_and_target(&href.unwrap_or_else(|| iframe.src()), "_blank")
+                    .map_err(js_error)?;
+                false
+            }
+        };
+
+        Ok(loading)
+    }
+
+    fn take_messages(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        self.take_from_script("takeMessages")
+    }
+
+    fn take_events(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        self.take_from_script("takeEvents")
+    }
+
+    fn take_status(&self) -> Result<Vec<HtmlMessage>, HframeError> {
+        self.take_from_script("takeStatus")
+    }
+
+    fn remove_element(&self, id: &str) -> Result<(), HframeError> {
+        if let Some(element) = self.document()?.get_element_by_id(id) {
+            element.remove();
+        }
+
+        Ok(())
+    }
+
+    fn canvas_rect(&self, canvas_id: Option<&str>) -> Result<Option<egui::Rect>, HframeError> {
+        let window = self.window()?;
+        let document = self.document()?;
+
+        let canvas = match canvas_id {
+            Some(id) => document.get_element_by_id(id),
+            None => document.query_selector("canvas").map_err(js_error)?,
+        };
+
+        let Some(canvas) = canvas else {
+            return Ok(None);
+        };
+
+        // Composed areas are absolute positioned in the body, so the canvas
+        // position must be relative to the document and not the viewport.
+        let rect = canvas.get_bounding_client_rect();
+        let scroll_x = window.scroll_x().map_err(js_error)?;
+        let scroll_y = window.scroll_y().map_err(js_error)?;
+
+        Ok(Some(egui::Rect::from_min_size(
+            egui::pos2(
+                (rect.left() + scroll_x) as f32,
+                (rect.top() + scroll_y) as f32,
+            ),
+            egui::vec2(rect.width() as f32, rect.height() as f32),
+        )))
+    }
+
+    fn debug(&self, label: &str, value: &str) {
+        web_sys::console::debug_2(&JsValue::from(label), &JsValue::from(value));
+    }
+}
@@ -0,0 +1,146 @@
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+
+/// Patches `from` in place to match `to`, like morphdom does.
+///
+/// Nodes present in both trees are kept alive, so they don't lose focus,
+/// selection, input values or loaded iframes. `to` may be consumed in the
+/// process.
+pub(super) fn morph(from: &web_sys::Node, to: &web_sys::Node) -> Result<(), JsValue> {
+    if from.node_type() != to.node_type() || from.node_name() != to.node_name() {
+        if let Some(parent) = from.parent_node() {
+            parent.replace_child(to, from)?;
+        }
+
+        return Ok(());
+    }
+
+    let Some(from_element) = from.dyn_ref::<web_sys::Element>() else {
+        // Text and comments.
+        if from.node_value() != to.node_value() {
+            from.set_node_value(to.node_value().as_deref());
+        }
+
+        return Ok(());
+    };
+
+    morph_attributes(from_element, to.unchecked_ref())?;
+
+    // The text of a textarea is only its default value, the user may have
+    // typed something else already.
+    if let Some(textarea) = from.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+        let text = to.text_content().unwrap_or_default();
+
+        if textarea.default_value()? != text {
+            textarea.set_default_value(&text)?;
+            textarea.set_value(&text);
+        }
+
+        return Ok(());
+    }
+
+    morph_children(from, to)
+}
+
+fn morph_attributes(from: &web_sys::Element, to: &web_sys::Element) -> Result<(), JsValue> {
+    let from_attributes = from.attributes();
+
+    // Backwards, as attributes are removed while iterating.
+    for i in (0..from_attributes.length()).rev() {
+        let Some(attribute) = from_attributes.item(i) else {
+            continue;
+        };
+
+        let name = attribute.name();
+        if !to.has_attribute(&name) {
+            from.remove_attribute(&name)?;
+            sync_input_state(from, &name, None);
+        }
+    }
+
+    let to_attributes = to.attributes();
+
+    for i in 0..to_attributes.length() {
+        let Some(attribute) = to_attributes.item(i) else {
+            continue;
+        };
+
+        let (name, value) = (attribute.name(), attribute.value());
+        if from.get_attribute(&name).as_deref() != Some(value.as_str()) {
+            from.set_attribute(&name, &value)?;
+            sync_input_state(from, &name, Some(&value));
+        }
+    }
+
+    Ok(())
+}
+
+/// Attributes of inputs are only their defaults once the user interacts with
+/// them, so changes are also applied to the current state. Unchanged
+/// attributes don't override what the user did.
+fn sync_input_state(element: &web_sys::Element, name: &str, value: Option<&str>) {
+    let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() else {
+        return;
+    };
+
+    match name {
+        "value" => input.set_value(value.unwrap_or_default()),
+        "checked" => input.set_checked(value.is_some()),
+        _ => {}
+    }
+}
+
+fn morph_children(from: &web_sys::Node, to: &web_sys::Node) -> Result<(), JsValue> {
+    let mut from_child = from.first_child();
+    let mut to_child = to.first_child();
+
+    while let Some(to_node) = to_child {
+        // `to_node` may be moved out of `to` below.
+        to_child = to_node.next_sibling();
+
+        // Elements with an id are matched by it, so they survive reordering.
+        if let Some(id) = element_id(&to_node) {
+            if from_child.as_ref().and_then(element_id).as_deref() != Some(id.as_str()) {
+                if let Some(matching) = find_child_by_id(from, &id) {
+                    from.insert_before(&matching, from_child.as_ref())?;
+                    from_child = Some(matching);
+                }
+            }
+        }
+
+        match from_child {
+            Some(from_node) => {
+                from_child = from_node.next_sibling();
+                morph(&from_node, &to_node)?;
+            }
+            None => {
+                from.append_child(&to_node)?;
+            }
+        }
+    }
+
+    while let Some(extra) = from_child {
+        from_child = extra.next_sibling();
+        from.remove_child(&extra)?;
+    }
+
+    Ok(())
+}
+
+fn element_id(node: &web_sys::Node) -> Option<String> {
+    let id = node.dyn_ref::<web_sys::Element>()?.id();
+    (!id.is_empty()).then_some(id)
+}
+
+fn find_child_by_id(parent: &web_sys::Node, id: &str) -> Option<web_sys::Node> {
+    let mut child = parent.first_child();
+
+    while let Some(node) = child {
+        if element_id(&node).as_deref() == Some(id) {
+            return Some(node);
+        }
+
+        child = node.next_sibling();
+    }
+
+    None
+}
@@ -0,0 +1,35 @@
+use std::fmt;
+
+/// Errors that can happen while hframe manipulates the DOM.
+///
+/// Most of them are caused by the page being modified by third-party scripts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HframeError {
+    NoWindow,
+    NoDocument,
+    NoHead,
+    NoBody,
+    /// An element managed by hframe was not found. It will be recreated on the
+    /// next frame.
+    ElementNotFound(String),
+    /// A JavaScript exception thrown by a DOM call.
+    Js(String),
+    /// A message couldn't be serialized to or deserialized from JSON.
+    Serialization(String),
+}
+
+impl fmt::Display for HframeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoWindow => write!(f, "No global window found"),
+            Self::NoDocument => write!(f, "No document found in the window"),
+            Self::NoHead => write!(f, "No head element found in the document"),
+            Self::NoBody => write!(f, "No body element found in the document"),
+            Self::ElementNotFound(id) => write!(f, "Element `{id}` not found in the document"),
+            Self::Js(message) => write!(f, "JavaScript error: {message}"),
+            Self::Serialization(message) => write!(f, "Serialization error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for HframeError {}
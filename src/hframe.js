@@ -0,0 +1,136 @@
+// Injected once by hframe. Collects the messages sent by HTML content, the
+// DOM events happening inside it and its status, like loaded iframes, until
+// the egui app takes them on its next sync.
+(() => {
+  const inbox = [];
+  const events = [];
+  // Only for hframe, not forwarded to the app.
+  const status = [];
+
+  // Id of the hframe element holding the given node.
+  const containerId = (node) => node?.closest?.(".hframe-composed-area")?.id;
+
+  const receive = (id, origin, message) => {
+    if (id) {
+      inbox.push([id, origin, JSON.stringify(message) ?? "null"]);
+    }
+  };
+
+  window.hframe = {
+    // Sends a message to the egui app from content living in the page.
+    // `target` is the id of the window, or any element inside it.
+    send(target, message) {
+      const id = typeof target === "string" ? target : containerId(target);
+      receive(id, null, message);
+    },
+
+    takeMessages() {
+      return inbox.splice(0);
+    },
+
+    takeEvents() {
+      return events.splice(0);
+    },
+
+    takeStatus() {
+      return status.splice(0);
+    },
+  };
+
+  // Same shape as `HtmlEvent` in Rust.
+  const describe = (event) => {
+    const target = event.target;
+
+    return {
+      kind: event.type,
+      target_id: target.id || null,
+      target_name: target.getAttribute?.("name") ?? null,
+      value: typeof target.value === "string" ? target.value : null,
+      checked: typeof target.checked === "boolean" ? target.checked : null,
+      form_data:
+        target instanceof HTMLFormElement
+          ? [...new FormData(target)].filter(([, v]) => typeof v === "string")
+          : [],
+    };
+  };
+
+  // Reports the title of same-origin documents, including srcdoc ones, every
+  // time it changes. Others have no `contentDocument`.
+  const watchTitle = (id, iframe) => {
+    const contentDocument = iframe.contentDocument;
+    if (!contentDocument?.documentElement) {
+      return;
+    }
+
+    let title = null;
+    const report = () => {
+      if (contentDocument.title !== title) {
+        title = contentDocument.title;
+        status.push([id, null, JSON.stringify({ kind: "title", title })]);
+      }
+    };
+
+    report();
+    new MutationObserver(report).observe(contentDocument.documentElement, {
+      subtree: true,
+      childList: true,
+      characterData: true,
+    });
+  };
+
+  // Captured, as focus and blur don't bubble. Events inside iframes are not
+  // visible from here.
+  for (const type of ["click", "input", "change", "submit", "focus", "blur"]) {
+    document.addEventListener(
+      type,
+      (event) => {
+        const id = containerId(event.target);
+
+        if (id) {
+          // Submitting would navigate away from the egui app.
+          if (type === "submit") {
+            event.preventDefault();
+          }
+
+          events.push([id, null, JSON.stringify(describe(event))]);
+        }
+      },
+      true,
+    );
+  }
+
+  // Same shape as `HtmlStatus` in Rust. Iframes fire `load` on every
+  // navigation, even when they show an error page instead, so `error` is
+  // rare.
+  for (const type of ["load", "error"]) {
+    document.addEventListener(
+      type,
+      (event) => {
+        const id = containerId(event.target);
+
+        if (id && event.target instanceof HTMLIFrameElement) {
+          status.push([id, null, JSON.stringify({ kind: type })]);
+
+          // Every navigation loads a new document.
+          if (type === "load") {
+            watchTitle(id, event.target);
+          }
+        }
+      },
+      true,
+    );
+  }
+
+  // Iframes talk through `window.parent.postMessage`.
+  window.addEventListener("message", (event) => {
+    const iframe = [
+      ...document.querySelectorAll(".hframe-composed-area iframe"),
+    ].find((iframe) => iframe.contentWindow === event.source);
+
+    if (iframe) {
+      const origin =
+        event.origin === window.location.origin ? null : event.origin;
+      receive(containerId(iframe), origin, event.data);
+    }
+  });
+})();
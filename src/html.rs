@@ -0,0 +1,102 @@
+use crate::{
+    get_composition_context, html_window::PLACEHOLDER, utils::egui::eid, AreaShape, ComposedArea,
+    ComposedHtml, ComposedHtmlStatus,
+};
+
+/// A widget displaying HTML content inside any egui `Ui`.
+///
+/// It can be placed in windows, panels, scroll areas, grids, etc.
+///
+/// ```rust,ignore
+/// ui.add(hframe::Html::new("docs", "<iframe src=\"https://docs.rs\"></iframe>"));
+/// ```
+///
+/// Note: The window holding this widget is not automatically aware, you must
+/// call `aware` on it if it can overlap with other HTML content.
+pub struct Html {
+    pub(crate) id: String,
+    pub(crate) content: String,
+    pub(crate) size: Option<egui::Vec2>,
+    pub(crate) allowed_origins: Vec<String>,
+}
+
+impl Html {
+    /// Create a new Html widget.
+    ///
+    /// The id must be unique as it's used as the id of the HTML element.
+    ///
+    /// As with `HtmlWindow`, the content is only re-rendered when it changes.
+    pub fn new(id: &str, content: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            content: content.to_string(),
+            size: None,
+            allowed_origins: Vec::new(),
+        }
+    }
+
+    /// Size of the widget. By default, it takes all the available space.
+    pub fn size(mut self, size: impl Into<egui::Vec2>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Accept messages from iframes with the given origin, like
+    /// `https://example.com`, or from any origin with `*`.
+    ///
+    /// Only messages from the page origin are accepted by default. Can be
+    /// called many times to allow several origins.
+    pub fn allowed_origin(mut self, origin: &str) -> Self {
+        self.allowed_origins.push(origin.to_string());
+        self
+    }
+}
+
+impl egui::Widget for Html {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let Self {
+            id,
+            content,
+            size,
+            allowed_origins,
+        } = self;
+
+        let size = size.unwrap_or_else(|| ui.available_size());
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+        ui.put(rect, egui::Label::new(PLACEHOLDER));
+
+        let area_id = eid!(&id);
+        let layer_id = ui.layer_id();
+        let clip_rect = ui.clip_rect();
+
+        let cmp = get_composition_context(ui.ctx());
+        let cmp = &mut *cmp.lock().unwrap();
+
+        let html_interactive = cmp.is_html_interactive(area_id, layer_id);
+
+        cmp.put_composed_area(ComposedArea {
+            id: area_id,
+            layer_id,
+            rect: rect.intersect(clip_rect),
+            shape: AreaShape::rect(),
+            html: Some(ComposedHtml {
+                id,
+                content,
+                rect,
+                clip_rect,
+                keep_alive: false,
+                allowed_origins,
+                iframe: None,
+                load_state: None,
+                load_started: 0.0,
+                title: None,
+                status: ComposedHtmlStatus {
+                    interactive: html_interactive,
+                    visible: ui.is_rect_visible(rect),
+                },
+            }),
+        });
+
+        response
+    }
+}
@@ -0,0 +1,31 @@
+/// A DOM event that happened inside HTML content, as returned by
+/// `take_events`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HtmlEvent {
+    pub kind: HtmlEventKind,
+    /// Id of the element targeted by the event, if it has one.
+    pub target_id: Option<String>,
+    /// `name` attribute of the element targeted by the event, like the one of
+    /// form fields.
+    pub target_name: Option<String>,
+    /// Current value of inputs, selects and textareas.
+    pub value: Option<String>,
+    /// Current state of checkboxes and radio buttons.
+    pub checked: Option<bool>,
+    /// Fields of submitted forms as name and value pairs. Files are left out.
+    pub form_data: Vec<(String, String)>,
+}
+
+/// Kinds of DOM events forwarded by hframe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HtmlEventKind {
+    Click,
+    Input,
+    Change,
+    /// Forms are not actually submitted, as that would navigate away from the
+    /// app.
+    Submit,
+    Focus,
+    Blur,
+}
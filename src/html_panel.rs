@@ -0,0 +1,45 @@
+use crate::Html;
+
+/// Fills egui panels with HTML content.
+///
+/// Floating windows are shown on top of the HTML as long as they are aware.
+///
+/// ```rust,ignore
+/// use hframe::HtmlPanel;
+///
+/// egui::SidePanel::left("docs-panel").show_html(ctx, "docs", DOCS);
+/// egui::CentralPanel::default().show_html(ctx, "map", MAP);
+/// ```
+pub trait HtmlPanel {
+    /// Shows the panel with the given HTML content taking all of its space.
+    ///
+    /// The id must be unique as it's used as the id of the HTML element.
+    ///
+    /// Only messages from the page origin are accepted. Add an [`Html`] widget
+    /// with `show` instead to allow other origins.
+    fn show_html(
+        self,
+        ctx: &egui::Context,
+        id: &str,
+        content: &str,
+    ) -> egui::InnerResponse<egui::Response>;
+}
+
+macro_rules! impl_html_panel {
+    ($($panel:ty),*) => {
+        $(
+            impl HtmlPanel for $panel {
+                fn show_html(
+                    self,
+                    ctx: &egui::Context,
+                    id: &str,
+                    content: &str,
+                ) -> egui::InnerResponse<egui::Response> {
+                    self.show(ctx, |ui| ui.add(Html::new(id, content)))
+                }
+            }
+        )*
+    };
+}
+
+impl_html_panel!(egui::CentralPanel, egui::SidePanel, egui::TopBottomPanel);
@@ -1,10 +1,40 @@
 use crate::{
-    get_composition_context, utils::egui::eid, ComposedArea, ComposedHtml, ComposedHtmlStatus,
+    get_composition_context, utils::egui::eid, AreaShape, ComposedArea, ComposedHtml,
+    ComposedHtmlStatus, Iframe, LoadState, Navigation,
 };
 
+/// Shown where the HTML content would be on targets without a DOM.
+#[cfg(target_arch = "wasm32")]
+pub(crate) const PLACEHOLDER: &str = "";
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const PLACEHOLDER: &str = "HTML content is only available on the web";
+
+/// HTML specific state of a shown `HtmlWindow`.
+#[derive(Clone, Debug)]
+pub struct HtmlWindowResponse {
+    /// Rect where the HTML content is placed. `None` if the window is collapsed.
+    pub html_rect: Option<egui::Rect>,
+    /// Whether the HTML content is visible.
+    pub visible: bool,
+    /// Whether the HTML content receives pointer events.
+    pub interactive: bool,
+    /// Whether the HTML content was (re-)rendered this frame, because it was
+    /// shown for the first time or its content changed.
+    pub rerendered: bool,
+    /// Loading state of the iframe, for windows created with `iframe` or
+    /// `url`.
+    pub load_state: Option<LoadState>,
+}
+
+/// Deferred call to one of egui's Window builder methods.
+type WindowOption<'open> = Box<dyn FnOnce(egui::Window<'open>) -> egui::Window<'open> + 'open>;
+
 /// A window capable of displaying HTML content inside.
 ///
-/// It's API mimics egui's Window API.
+/// It's API mimics egui's Window API. The scrolling options of egui's Window,
+/// `scroll2`, `hscroll`, `vscroll` and `drag_to_scroll`, are deliberately
+/// unsupported, as the HTML content wouldn't follow the scrolled egui content
+/// nor be clipped by the scroll area. Scroll inside the HTML content instead.
 ///
 /// Note: `hframe` is automatically aware of this window.
 pub struct HtmlWindow<'open> {
@@ -12,6 +42,17 @@ pub struct HtmlWindow<'open> {
     pub(crate) title: String,
     pub(crate) content: String,
     pub(crate) open: Option<&'open mut bool>,
+    pub(crate) keep_alive: bool,
+    pub(crate) allowed_origins: Vec<String>,
+    /// The iframe making up the content, if built by hframe.
+    pub(crate) iframe: Option<Iframe>,
+    pub(crate) loading_indicator: bool,
+    pub(crate) navigation_bar: bool,
+    pub(crate) title_from_content: bool,
+    /// Custom frame of the window, also giving the shape of its holes.
+    pub(crate) frame: Option<egui::Frame>,
+    /// Applied in order to the inner egui Window when shown.
+    pub(crate) window_options: Vec<WindowOption<'open>>,
 }
 
 impl<'open> HtmlWindow<'open> {
@@ -27,6 +68,14 @@ impl<'open> HtmlWindow<'open> {
             title: title.to_string(),
             content: "".into(),
             open: None,
+            keep_alive: false,
+            allowed_origins: Vec::new(),
+            iframe: None,
+            loading_indicator: false,
+            navigation_bar: false,
+            title_from_content: false,
+            frame: None,
+            window_options: Vec::new(),
         }
     }
 
@@ -48,27 +97,293 @@ impl<'open> HtmlWindow<'open> {
     /// As long as the HTML doesn't change, this will not re-render the content.
     ///
     /// If you change the content, then the HTML will be re-rendered which is
-    /// useful if you need to display controlled and reactive content.
+    /// useful if you need to display controlled and reactive content. Only
+    /// the changed nodes are patched, so the rest keep their state.
     pub fn content(mut self, content: &str) -> Self {
         self.content = content.to_string();
+        self.iframe = None;
+        self
+    }
+
+    /// Set the content of the window to an iframe built with [`Iframe`].
+    ///
+    /// Changing the URL later navigates the existing iframe by updating its
+    /// `src`, without re-rendering the rest of the content.
+    ///
+    /// Its loading state is tracked and reported in the window response.
+    pub fn iframe(self, iframe: Iframe) -> Self {
+        let mut window = self.content(&iframe.to_html());
+        window.iframe = Some(iframe);
+        window
+    }
+
+    /// Set the content of the window to an iframe loading the given URL. Use
+    /// `iframe` for more options.
+    pub fn url(self, url: &str) -> Self {
+        self.iframe(Iframe::new(url))
+    }
+
+    /// Draw a spinner where the iframe goes while it loads, and an error
+    /// message if it fails to load. Only for windows created with `iframe` or
+    /// `url`.
+    pub fn loading_indicator(mut self, loading_indicator: bool) -> Self {
+        self.loading_indicator = loading_indicator;
+        self
+    }
+
+    /// Show back, forward, reload and open in new tab buttons above the
+    /// content. Only for windows created with `iframe` or `url`, see
+    /// `navigate` for other content.
+    pub fn navigation_bar(mut self, navigation_bar: bool) -> Self {
+        self.navigation_bar = navigation_bar;
+        self
+    }
+
+    /// Show the title of the document inside the iframe as the window title,
+    /// once known. Only same-origin and `srcdoc` iframes report their title,
+    /// other documents show the title given to `new`.
+    ///
+    /// The id of the window is still derived from the title given to `new`.
+    pub fn title_from_content(mut self, title_from_content: bool) -> Self {
+        self.title_from_content = title_from_content;
+        self
+    }
+
+    /// Keep the HTML element alive while the window is not shown, so iframes,
+    /// videos and forms don't lose their state when it's shown again.
+    ///
+    /// The element is hidden until then. Use `evict` to remove it for good.
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Accept messages from iframes with the given origin, like
+    /// `https://example.com`, or from any origin with `*`.
+    ///
+    /// Only messages from the page origin are accepted by default. Can be
+    /// called many times to allow several origins.
+    pub fn allowed_origin(mut self, origin: &str) -> Self {
+        self.allowed_origins.push(origin.to_string());
+        self
+    }
+
+    /// Mimics the `default_pos` method of egui's Window.
+    pub fn default_pos(self, default_pos: impl Into<egui::Pos2>) -> Self {
+        let default_pos = default_pos.into();
+        self.window_option(move |window| window.default_pos(default_pos))
+    }
+
+    /// Mimics the `current_pos` method of egui's Window.
+    pub fn current_pos(self, current_pos: impl Into<egui::Pos2>) -> Self {
+        let current_pos = current_pos.into();
+        self.window_option(move |window| window.current_pos(current_pos))
+    }
+
+    /// Mimics the `fixed_pos` method of egui's Window.
+    pub fn fixed_pos(self, pos: impl Into<egui::Pos2>) -> Self {
+        let pos = pos.into();
+        self.window_option(move |window| window.fixed_pos(pos))
+    }
+
+    /// Mimics the `default_size` method of egui's Window.
+    pub fn default_size(self, default_size: impl Into<egui::Vec2>) -> Self {
+        let default_size = default_size.into();
+        self.window_option(move |window| window.default_size(default_size))
+    }
+
+    /// Mimics the `default_width` method of egui's Window.
+    pub fn default_width(self, default_width: f32) -> Self {
+        self.window_option(move |window| window.default_width(default_width))
+    }
+
+    /// Mimics the `default_height` method of egui's Window.
+    pub fn default_height(self, default_height: f32) -> Self {
+        self.window_option(move |window| window.default_height(default_height))
+    }
+
+    /// Mimics the `fixed_size` method of egui's Window.
+    pub fn fixed_size(self, size: impl Into<egui::Vec2>) -> Self {
+        let size = size.into();
+        self.window_option(move |window| window.fixed_size(size))
+    }
+
+    /// Mimics the `default_rect` method of egui's Window.
+    pub fn default_rect(self, rect: egui::Rect) -> Self {
+        self.window_option(move |window| window.default_rect(rect))
+    }
+
+    /// Mimics the `fixed_rect` method of egui's Window.
+    pub fn fixed_rect(self, rect: egui::Rect) -> Self {
+        self.window_option(move |window| window.fixed_rect(rect))
+    }
+
+    /// Mimics the `min_width` method of egui's Window.
+    pub fn min_width(self, min_width: f32) -> Self {
+        self.window_option(move |window| window.min_width(min_width))
+    }
+
+    /// Mimics the `min_height` method of egui's Window.
+    pub fn min_height(self, min_height: f32) -> Self {
+        self.window_option(move |window| window.min_height(min_height))
+    }
+
+    /// Mimics the `min_size` method of egui's Window.
+    pub fn min_size(self, min_size: impl Into<egui::Vec2>) -> Self {
+        let min_size = min_size.into();
+        self.window_option(move |window| window.min_size(min_size))
+    }
+
+    /// Mimics the `max_width` method of egui's Window.
+    pub fn max_width(self, max_width: f32) -> Self {
+        self.window_option(move |window| window.max_width(max_width))
+    }
+
+    /// Mimics the `max_height` method of egui's Window.
+    pub fn max_height(self, max_height: f32) -> Self {
+        self.window_option(move |window| window.max_height(max_height))
+    }
+
+    /// Mimics the `max_size` method of egui's Window.
+    pub fn max_size(self, max_size: impl Into<egui::Vec2>) -> Self {
+        let max_size = max_size.into();
+        self.window_option(move |window| window.max_size(max_size))
+    }
+
+    /// Mimics the `resizable` method of egui's Window.
+    pub fn resizable(self, resizable: bool) -> Self {
+        self.window_option(move |window| window.resizable(resizable))
+    }
+
+    /// Mimics the `resize` method of egui's Window.
+    pub fn resize(self, mutate: impl Fn(egui::Resize) -> egui::Resize + 'open) -> Self {
+        self.window_option(move |window| window.resize(mutate))
+    }
+
+    /// Mimics the `auto_sized` method of egui's Window.
+    ///
+    /// HTML content doesn't have a size of its own, so show it with
+    /// `HtmlSlot::show_sized` through `show_with` to size the window.
+    pub fn auto_sized(self) -> Self {
+        self.window_option(|window| window.auto_sized())
+    }
+
+    /// Mimics the `collapsible` method of egui's Window.
+    pub fn collapsible(self, collapsible: bool) -> Self {
+        self.window_option(move |window| window.collapsible(collapsible))
+    }
+
+    /// Mimics the `default_open` method of egui's Window.
+    pub fn default_open(self, default_open: bool) -> Self {
+        self.window_option(move |window| window.default_open(default_open))
+    }
+
+    /// Mimics the `title_bar` method of egui's Window.
+    pub fn title_bar(self, title_bar: bool) -> Self {
+        self.window_option(move |window| window.title_bar(title_bar))
+    }
+
+    /// Mimics the `movable` method of egui's Window.
+    pub fn movable(self, movable: bool) -> Self {
+        self.window_option(move |window| window.movable(movable))
+    }
+
+    /// Mimics the `constrain` method of egui's Window.
+    pub fn constrain(self, constrain: bool) -> Self {
+        self.window_option(move |window| window.constrain(constrain))
+    }
+
+    /// Mimics the `constrain_to` method of egui's Window.
+    pub fn constrain_to(self, constrain_rect: egui::Rect) -> Self {
+        self.window_option(move |window| window.constrain_to(constrain_rect))
+    }
+
+    /// Mimics the `pivot` method of egui's Window.
+    pub fn pivot(self, pivot: egui::Align2) -> Self {
+        self.window_option(move |window| window.pivot(pivot))
+    }
+
+    /// Mimics the `frame` method of egui's Window.
+    pub fn frame(mut self, frame: egui::Frame) -> Self {
+        self.frame = Some(frame);
+        self.window_option(move |window| window.frame(frame))
+    }
+
+    /// Mimics the `enabled` method of egui's Window.
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.window_option(move |window| window.enabled(enabled))
+    }
+
+    /// Mimics the `interactable` method of egui's Window.
+    pub fn interactable(self, interactable: bool) -> Self {
+        self.window_option(move |window| window.interactable(interactable))
+    }
+
+    /// Mimics the `anchor` method of egui's Window.
+    pub fn anchor(self, align: egui::Align2, offset: impl Into<egui::Vec2>) -> Self {
+        let offset = offset.into();
+        self.window_option(move |window| window.anchor(align, offset))
+    }
+
+    fn window_option(
+        mut self,
+        option: impl FnOnce(egui::Window<'open>) -> egui::Window<'open> + 'open,
+    ) -> Self {
+        self.window_options.push(Box::new(option));
         self
     }
 
     /// Displays the window and it's content.
     ///
+    /// Like egui's Window, returns `None` if the window is closed.
+    ///
     /// Note: You will still need to call `sync` at the end of the update loop
     /// to make this work propertly.
-    pub fn show(self, ctx: &egui::Context) {
+    pub fn show(self, ctx: &egui::Context) -> Option<egui::InnerResponse<HtmlWindowResponse>> {
+        self.show_with(ctx, |ui, html_slot| {
+            html_slot.show(ui);
+        })
+    }
+
+    /// Displays the window letting you add egui widgets around the HTML
+    /// content, like toolbars or status bars.
+    ///
+    /// The HTML content is placed wherever you show the given `HtmlSlot`. If
+    /// you don't, the HTML content is hidden.
+    ///
+    /// ```rust,ignore
+    /// hframe::HtmlWindow::new("Docs")
+    ///     .content(DOCS)
+    ///     .show_with(ctx, |ui, html_slot| {
+    ///         ui.horizontal(|ui| {
+    ///             ui.button("Home");
+    ///         });
+    ///         html_slot.show(ui);
+    ///     });
+    /// ```
+    pub fn show_with(
+        self,
+        ctx: &egui::Context,
+        add_contents: impl FnOnce(&mut egui::Ui, &mut HtmlSlot),
+    ) -> Option<egui::InnerResponse<HtmlWindowResponse>> {
         let Self {
             id,
             title,
             content,
             open,
+            keep_alive,
+            allowed_origins,
+            iframe,
+            loading_indicator,
+            navigation_bar,
+            title_from_content,
+            frame,
+            window_options,
         } = self;
 
         let open = if let Some(open) = open {
             if !*open {
-                return;
+                return None;
             }
 
             Some(open)
@@ -76,46 +391,186 @@ impl<'open> HtmlWindow<'open> {
             None
         };
 
+        // Known since the previous frame.
+        let (load_state, content_title) = {
+            let cmp = get_composition_context(ctx);
+            let cmp = cmp.lock().unwrap();
+            let load_state = iframe
+                .as_ref()
+                .map(|_| cmp.load_state(&id).unwrap_or(LoadState::Loading));
+            let content_title = cmp
+                .content_title(&id)
+                .filter(|title| title_from_content && !title.is_empty())
+                .map(str::to_string);
+
+            (load_state, content_title)
+        };
+        let title = content_title.unwrap_or(title);
+
         // tel ctx to render html here
 
-        let window = egui::Window::new(title).id(eid!(&id));
+        let window = window_options
+            .into_iter()
+            .fold(egui::Window::new(title).id(eid!(&id)), |window, option| {
+                option(window)
+            });
         let window = match open {
             Some(open) => window.open(open),
             None => window,
         };
 
         let shown_window = window.show(ctx, |ui| {
-            ui.centered_and_justified(|ui| {
-                ui.label("");
-            })
-            .response
-            .rect
+            let mut html_slot = HtmlSlot {
+                load_state: load_state.filter(|_| loading_indicator),
+                ..Default::default()
+            };
+
+            if navigation_bar && iframe.is_some() {
+                add_navigation_bar(ui, &id);
+                ui.separator();
+            }
+
+            add_contents(ui, &mut html_slot);
+            html_slot
         });
 
-        if let Some(inner_response) = shown_window {
-            let cmp = get_composition_context(ctx);
-            let cmp = &mut *cmp.lock().unwrap();
-            let ctx = &cmp.egui_ctx;
-
-            let html_visible = inner_response.inner.is_some();
-            let html_rect = inner_response.inner.unwrap_or(egui::Rect::ZERO);
-            let html_interactive = ctx
-                .input(|i| !i.pointer.button_down(egui::PointerButton::Primary))
-                && ctx.top_layer_id() == Some(inner_response.response.layer_id);
-
-            cmp.put_composed_area(ComposedArea {
-                id: inner_response.response.layer_id.id,
-                rect: inner_response.response.rect,
-                html: Some(ComposedHtml {
-                    id,
-                    content,
-                    rect: html_rect,
-                    status: ComposedHtmlStatus {
-                        interactive: html_interactive,
-                        visible: html_visible,
-                    },
-                }),
-            })
+        let inner_response = shown_window?;
+
+        let cmp = get_composition_context(ctx);
+        let cmp = &mut *cmp.lock().unwrap();
+        let ctx = &cmp.egui_ctx;
+
+        // The window may be collapsed or the slot may not be shown.
+        let html_slot = inner_response.inner.unwrap_or_default();
+        let html_visible = html_slot.rect.is_some();
+        let html_rect = html_slot.rect.unwrap_or(egui::Rect::ZERO);
+        let layer_id = inner_response.response.layer_id;
+        let html_interactive = cmp.is_html_interactive(layer_id.id, layer_id);
+
+        let html_id = id.clone();
+        let rerendered = cmp.put_composed_area(ComposedArea {
+            id: layer_id.id,
+            layer_id,
+            rect: inner_response.response.rect,
+            shape: match &frame {
+                Some(frame) => AreaShape::frame(frame),
+                None => AreaShape::window(&ctx.style()),
+            },
+            html: Some(ComposedHtml {
+                id,
+                content,
+                rect: html_rect,
+                clip_rect: html_slot.clip_rect,
+                keep_alive,
+                allowed_origins,
+                iframe,
+                load_state: None,
+                load_started: 0.0,
+                title: None,
+                status: ComposedHtmlStatus {
+                    interactive: html_interactive,
+                    visible: html_visible,
+                },
+            }),
+        });
+
+        Some(egui::InnerResponse::new(
+            HtmlWindowResponse {
+                html_rect: html_slot.rect,
+                visible: html_visible,
+                interactive: html_interactive,
+                rerendered,
+                load_state: cmp.load_state(&html_id),
+            },
+            inner_response.response,
+        ))
+    }
+}
+
+fn add_navigation_bar(ui: &mut egui::Ui, html_id: &str) {
+    let buttons = [
+        ("⏴", "Back", Navigation::Back),
+        ("⏵", "Forward", Navigation::Forward),
+        ("⟳", "Reload", Navigation::Reload),
+        ("↗", "Open in new tab", Navigation::OpenInNewTab),
+    ];
+
+    ui.horizontal(|ui| {
+        for (icon, hint, navigation) in buttons {
+            if ui.button(icon).on_hover_text(hint).clicked() {
+                crate::navigate(ui.ctx(), html_id, navigation);
+            }
+        }
+    });
+}
+
+/// The place where the HTML content of an `HtmlWindow` goes.
+///
+/// See `HtmlWindow::show_with`.
+pub struct HtmlSlot {
+    rect: Option<egui::Rect>,
+    clip_rect: egui::Rect,
+    /// Shown behind the HTML content if set.
+    load_state: Option<LoadState>,
+}
+
+impl Default for HtmlSlot {
+    fn default() -> Self {
+        Self {
+            rect: None,
+            clip_rect: egui::Rect::EVERYTHING,
+            load_state: None,
+        }
+    }
+}
+
+impl HtmlSlot {
+    /// Places the HTML content taking all the available space.
+    pub fn show(&mut self, ui: &mut egui::Ui) -> egui::Response {
+        let response = ui
+            .centered_and_justified(|ui| self.add_placeholder(ui))
+            .response;
+
+        self.place(ui, response.rect);
+        response
+    }
+
+    /// Places the HTML content with the given size.
+    pub fn show_sized(&mut self, ui: &mut egui::Ui, size: impl Into<egui::Vec2>) -> egui::Response {
+        let (rect, response) = ui.allocate_exact_size(size.into(), egui::Sense::hover());
+        ui.allocate_ui_at_rect(rect, |ui| {
+            ui.centered_and_justified(|ui| self.add_placeholder(ui));
+        });
+
+        self.place(ui, rect);
+        response
+    }
+
+    /// Painted behind the HTML content, so it's only seen while there is
+    /// nothing on top of it, like while an iframe loads.
+    fn add_placeholder(&self, ui: &mut egui::Ui) {
+        // Nothing is ever loaded on targets without a DOM.
+        if !cfg!(target_arch = "wasm32") {
+            ui.label(PLACEHOLDER);
+            return;
+        }
+
+        match &self.load_state {
+            Some(LoadState::Loading) => {
+                ui.spinner();
+            }
+            Some(LoadState::Failed(reason)) => {
+                let error_color = ui.visuals().error_fg_color;
+                ui.colored_label(error_color, format!("Failed to load the content: {reason}"));
+            }
+            _ => {
+                ui.label(PLACEHOLDER);
+            }
         }
     }
+
+    fn place(&mut self, ui: &egui::Ui, rect: egui::Rect) {
+        self.rect = Some(rect);
+        self.clip_rect = ui.clip_rect();
+    }
 }
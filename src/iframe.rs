@@ -0,0 +1,255 @@
+/// Builder for iframe elements, to avoid writing them as raw HTML strings.
+///
+/// ```rust,ignore
+/// let docs = hframe::Iframe::new("https://docs.rs/")
+///     .sandbox([hframe::Sandbox::AllowScripts])
+///     .loading(hframe::Loading::Lazy);
+///
+/// hframe::HtmlWindow::new("Docs").iframe(docs).show(ctx);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Iframe {
+    src: String,
+    srcdoc: Option<String>,
+    sandbox: Option<Vec<Sandbox>>,
+    allow: Option<String>,
+    referrer_policy: Option<ReferrerPolicy>,
+    loading: Option<Loading>,
+    title: Option<String>,
+}
+
+impl Iframe {
+    /// Create an iframe loading the given URL.
+    pub fn new(src: &str) -> Self {
+        Self {
+            src: src.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Create an iframe showing the given inline HTML document.
+    pub fn from_srcdoc(srcdoc: &str) -> Self {
+        Self::default().srcdoc(srcdoc)
+    }
+
+    /// Inline HTML document to show instead of loading `src`.
+    pub fn srcdoc(mut self, srcdoc: &str) -> Self {
+        self.srcdoc = Some(srcdoc.to_string());
+        self
+    }
+
+    /// Restricts the content of the iframe, lifting only the given
+    /// restrictions. An empty list applies every restriction.
+    pub fn sandbox(mut self, flags: impl IntoIterator<Item = Sandbox>) -> Self {
+        self.sandbox = Some(flags.into_iter().collect());
+        self
+    }
+
+    /// Permissions policy of the iframe, like `"fullscreen; autoplay"`.
+    pub fn allow(mut self, allow: &str) -> Self {
+        self.allow = Some(allow.to_string());
+        self
+    }
+
+    /// Referrer sent when loading the iframe.
+    pub fn referrer_policy(mut self, referrer_policy: ReferrerPolicy) -> Self {
+        self.referrer_policy = Some(referrer_policy);
+        self
+    }
+
+    /// When the iframe is loaded.
+    pub fn loading(mut self, loading: Loading) -> Self {
+        self.loading = Some(loading);
+        self
+    }
+
+    /// Accessible title of the iframe.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// The URL loaded by the iframe.
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+
+    /// Whether both iframes show the same document, as only changing `src`
+    /// or `srcdoc` navigates an iframe.
+    pub(crate) fn loads_same_document(&self, other: &Iframe) -> bool {
+        self.src == other.src && self.srcdoc == other.srcdoc
+    }
+
+    /// The iframe element as HTML.
+    pub fn to_html(&self) -> String {
+        let sandbox = self.sandbox.as_ref().map(|flags| {
+            flags
+                .iter()
+                .map(|flag| flag.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+
+        let attributes = [
+            ("src", (!self.src.is_empty()).then_some(self.src.as_str())),
+            ("srcdoc", self.srcdoc.as_deref()),
+            ("sandbox", sandbox.as_deref()),
+            ("allow", self.allow.as_deref()),
+            (
+                "referrerpolicy",
+                self.referrer_policy.as_ref().map(ReferrerPolicy::as_str),
+            ),
+            ("loading", self.loading.as_ref().map(Loading::as_str)),
+            ("title", self.title.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some(format!(r#" {name}="{}""#, escape_attribute(value?))))
+        .collect::<String>();
+
+        format!("<iframe{attributes}></iframe>")
+    }
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Loading state of an iframe created with [`Iframe`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadState {
+    Loading,
+    /// A document was loaded. Browsers don't tell apart error pages shown
+    /// when the content fails to load.
+    Loaded,
+    /// The iframe fired an `error` event or didn't load in time, see
+    /// [`set_load_timeout`](crate::set_load_timeout). Holds the reason.
+    Failed(String),
+}
+
+/// Navigation of the iframe making up some HTML content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Navigation {
+    /// Only for same-origin content.
+    Back,
+    /// Only for same-origin content.
+    Forward,
+    /// Loads `src` again if the content is not same-origin.
+    Reload,
+    /// Opens the current page in a new tab. Opens `src` if the content is not
+    /// same-origin.
+    OpenInNewTab,
+}
+
+/// Restrictions lifted from a sandboxed iframe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sandbox {
+    AllowDownloads,
+    AllowForms,
+    AllowModals,
+    AllowOrientationLock,
+    AllowPointerLock,
+    AllowPopups,
+    AllowPopupsToEscapeSandbox,
+    AllowPresentation,
+    AllowSameOrigin,
+    AllowScripts,
+    AllowStorageAccessByUserActivation,
+    AllowTopNavigation,
+    AllowTopNavigationByUserActivation,
+    AllowTopNavigationToCustomProtocols,
+}
+
+impl Sandbox {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AllowDownloads => "allow-downloads",
+            Self::AllowForms => "allow-forms",
+            Self::AllowModals => "allow-modals",
+            Self::AllowOrientationLock => "allow-orientation-lock",
+            Self::AllowPointerLock => "allow-pointer-lock",
+            Self::AllowPopups => "allow-popups",
+            Self::AllowPopupsToEscapeSandbox => "allow-popups-to-escape-sandbox",
+            Self::AllowPresentation => "allow-presentation",
+            Self::AllowSameOrigin => "allow-same-origin",
+            Self::AllowScripts => "allow-scripts",
+            Self::AllowStorageAccessByUserActivation => "allow-storage-access-by-user-activation",
+            Self::AllowTopNavigation => "allow-top-navigation",
+            Self::AllowTopNavigationByUserActivation => "allow-top-navigation-by-user-activation",
+            Self::AllowTopNavigationToCustomProtocols => "allow-top-navigation-to-custom-protocols",
+        }
+    }
+}
+
+/// Referrer sent when loading an iframe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferrerPolicy {
+    NoReferrer,
+    NoReferrerWhenDowngrade,
+    Origin,
+    OriginWhenCrossOrigin,
+    SameOrigin,
+    StrictOrigin,
+    StrictOriginWhenCrossOrigin,
+    UnsafeUrl,
+}
+
+impl ReferrerPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NoReferrer => "no-referrer",
+            Self::NoReferrerWhenDowngrade => "no-referrer-when-downgrade",
+            Self::Origin => "origin",
+            Self::OriginWhenCrossOrigin => "origin-when-cross-origin",
+            Self::SameOrigin => "same-origin",
+            Self::StrictOrigin => "strict-origin",
+            Self::StrictOriginWhenCrossOrigin => "strict-origin-when-cross-origin",
+            Self::UnsafeUrl => "unsafe-url",
+        }
+    }
+}
+
+/// When an iframe is loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Loading {
+    /// Right away.
+    Eager,
+    /// Once it's about to be visible.
+    Lazy,
+}
+
+impl Loading {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Eager => "eager",
+            Self::Lazy => "lazy",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Iframe, Loading, Sandbox};
+
+    #[test]
+    fn renders_typed_options_as_attributes() {
+        let iframe = Iframe::new("https://example.com/?a=1&b=2")
+            .sandbox([Sandbox::AllowScripts, Sandbox::AllowForms])
+            .loading(Loading::Lazy)
+            .title(r#"The "example""#);
+
+        assert_eq!(
+            iframe.to_html(),
+            r#"<iframe src="https://example.com/?a=1&amp;b=2" sandbox="allow-scripts allow-forms" loading="lazy" title="The &quot;example&quot;"></iframe>"#
+        );
+    }
+
+    #[test]
+    fn empty_sandbox_applies_every_restriction() {
+        let iframe = Iframe::new("https://example.com/").sandbox([]);
+        assert!(iframe.to_html().contains(r#"sandbox="""#));
+    }
+}
@@ -1,6 +1,7 @@
 //! Show HTML content "inside" your [egui](https://github.com/emilk/egui) rendered application. "hframe" stands for "HTML Frame".
 //!
 //! > **💡 Note:** `hframe` only works when the application is compiled to WebAssembly and run in a browser. But you can still make a desktop up by leveraging [Tauri](https://tauri.app/).
+//! > On other targets hframe still compiles, but HTML windows only show a placeholder label.
 //! >
 //! > [egui-tauri-template](https://github.com/noxware/egui-tauri-template)
 //! > will serve as good foundation if you need to target both platforms.
@@ -12,7 +13,7 @@
 //!
 //! ## Simple example
 //!
-//! ```rust
+//! ```rust,ignore
 //! use hframe::Aware;
 //!
 //! const IFRAME: &str = r#"
@@ -56,28 +57,146 @@
 //! This approach has been chosen after many experiments with alternative techniques
 //! which were not able to provide the same level of integration and flexibility.
 //!
+//! Positions are mapped from egui points to CSS pixels taking into account where the
+//! canvas is in the page, egui's zoom, the device pixel ratio and CSS scaling of the
+//! canvas. The first canvas of the document is used unless `set_canvas_id` is called.
+//!
 //! Additionally, some tracking is performed to provide an immediate mode like public
 //! API. HTML will only be re-rendered if you change the initially provided content. This
 //! can be useful if you want to change the HTML content in a controlled and reactive
-//! manner without giving up the immediate mode API.
+//! manner without giving up the immediate mode API. Changes are patched into the existing
+//! elements, so unchanged nodes keep their state, like focus, input values or loaded iframes.
+//!
+//! ## Composition strategies
+//!
+//! The technique used to show egui areas on top of HTML content can be changed
+//! at any time with [`set_composition_strategy`]. Built-in strategies live in
+//! [`composition_strategies`] but you can also implement [`CompositionStrategy`]
+//! yourself.
+//!
+//! ```rust,ignore
+//! hframe::set_composition_strategy(ctx, hframe::composition_strategies::ClipPath::new());
+//! ```
+//!
+//! ## HTML in any `Ui`
+//!
+//! Besides `HtmlWindow`, the `Html` widget lets you put HTML content anywhere inside
+//! an egui `Ui`, like panels, scroll areas or grids.
+//!
+//! ```rust,ignore
+//! egui::Window::new("Docs")
+//!     .show(ctx, |ui| {
+//!         ui.label("Some egui content");
+//!         ui.add(hframe::Html::new("docs", IFRAME));
+//!     })
+//!     .aware();
+//! ```
 //!
-//! ## Limitations
+//! Panels can be filled with HTML content directly through the `HtmlPanel` trait.
 //!
-//! - The current implementation assumes that the canvas takes the whole screen and
-//!   it is not scaled. The default configuration of the [eframe_template](https://github.com/emilk/eframe_template) will work.
-//! - Currently the API only provides a way to create egui windows with HTML "inside"
-//!   but doesn't provide a way to put bare HTML content in other places.
+//! ```rust,ignore
+//! use hframe::HtmlPanel;
+//!
+//! egui::CentralPanel::default().show_html(ctx, "map", MAP);
+//! ```
+//!
+//! ## Iframes
+//!
+//! Instead of writing `<iframe>` elements by hand, windows can be given a URL, or an
+//! `Iframe` with typed options like `sandbox`, `allow`, `referrerpolicy`, `loading`
+//! and `srcdoc`. Changing the URL navigates the existing iframe. The window response
+//! tells whether the iframe is loading, loaded or failed to load, either because of an
+//! `error` event or a timeout set with `set_load_timeout`. `loading_indicator` draws a
+//! spinner in its place while it loads, or an error message if it fails.
+//!
+//! `navigation_bar` adds back, forward, reload and open in new tab buttons to the window,
+//! and `navigate` does the same from your own widgets. Going back and forward only works
+//! for same-origin content, whose history is accessible. Other iframes are reloaded by
+//! setting their `src` again.
+//!
+//! With `title_from_content`, windows show the title of the document inside same-origin
+//! and `srcdoc` iframes as their title, while keeping the id derived from the original one.
+//!
+//! ```rust,ignore
+//! hframe::HtmlWindow::new("Docs").url("https://docs.rs/").show(ctx);
+//!
+//! hframe::HtmlWindow::new("Sandboxed")
+//!     .iframe(hframe::Iframe::new(URL).sandbox([hframe::Sandbox::AllowScripts]))
+//!     .show(ctx);
+//! ```
+//!
+//! ## Talking to HTML content
+//!
+//! `post_message` sends any serializable value to the HTML content of a window or
+//! widget, by id, without re-rendering it. Iframes receive it through `postMessage` once
+//! loaded, and only while their document has the origin of their `src`. Other content
+//! receives it as a `hframe-message` event dispatched on its container.
+//!
+//! ```rust,ignore
+//! hframe::post_message(ctx, "counter", &count)?;
+//! ```
+//!
+//! ```js
+//! document.getElementById("counter").addEventListener("hframe-message", (event) => {
+//!     console.log(event.detail);
+//! });
+//! ```
+//!
+//! HTML content talks back with the injected `hframe.send` helper, or with
+//! `window.parent.postMessage` from iframes. The app reads the messages on the next
+//! frame with `take_messages`. Iframe messages are only accepted from the page origin,
+//! unless more origins are allowed with `allowed_origin` on `HtmlWindow` or `Html`.
+//!
+//! ```html
+//! <button onclick="hframe.send(this, { clicked: true })">Click me</button>
+//! ```
+//!
+//! ```rust,ignore
+//! for message in hframe::take_messages::<serde_json::Value>(ctx, "counter") {
+//!     // ...
+//! }
+//! ```
+//!
+//! Common DOM events (click, input, change, submit, focus and blur) happening inside
+//! HTML content in the page are also forwarded, so simple forms can drive egui state
+//! without writing JavaScript. Forms are not actually submitted.
+//!
+//! ```rust,ignore
+//! for event in hframe::take_events(ctx, "signup") {
+//!     if event.kind == hframe::HtmlEventKind::Submit {
+//!         // event.form_data holds the form fields.
+//!     }
+//! }
+//! ```
 
+mod area_shape;
 mod aware;
+mod canvas_transform;
 mod composed_area;
 mod composition_context;
-pub(crate) mod composition_strategies;
+pub mod composition_strategies;
 mod composition_strategy;
+mod dom_backend;
+pub mod dom_backends;
+mod error;
+mod html;
+mod html_event;
+mod html_panel;
 mod html_window;
+mod iframe;
 pub(crate) mod utils;
 
+pub use area_shape::*;
 pub use aware::*;
-pub(crate) use composed_area::*;
+pub use canvas_transform::*;
+pub use composed_area::*;
 pub use composition_context::*;
-pub(crate) use composition_strategy::*;
+pub use composition_strategy::*;
+pub use dom_backend::*;
+pub use error::*;
+pub use html::*;
+pub use html_event::*;
+pub use html_panel::*;
 pub use html_window::*;
+pub use iframe::*;
+pub use utils::browser_detection::BrowserEngine;
@@ -1,38 +1,103 @@
-fn user_agent() -> String {
-    web_sys::window()
-        .unwrap()
-        .navigator()
-        .user_agent()
-        .unwrap()
-        .to_lowercase()
+/// Browser engine the application is running on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrowserEngine {
+    /// Chrome, Chromium, Edge, Opera and friends.
+    Blink,
+    /// Firefox.
+    Gecko,
+    /// Safari and every browser on iOS.
+    WebKit,
+    Unknown,
 }
-// Safari (WebKit) example:
-// Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1.2 Safari/605.1.15
 
-// Safari Technical Preview (WebKit) example:
-// "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Safari/605.1.15" = $1
+impl BrowserEngine {
+    /// Detects the engine from the browser's user agent.
+    ///
+    /// Always `Unknown` outside of WebAssembly.
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        return user_agent().map_or(Self::Unknown, |ua| Self::from_user_agent(&ua));
 
-// Firefox (Gecko) example:
-// Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:123.0) Gecko/20100101 Firefox/123.0
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self::Unknown;
+    }
 
-// Chrome (Blink) example:
-// Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36
+    /// Parses the engine from a user agent string.
+    pub fn from_user_agent(ua: &str) -> Self {
+        let ua = ua.to_lowercase();
 
-// Chromium (Blink) example:
-// Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36
+        if ua.contains("chrome") {
+            Self::Blink
+        } else if ua.contains("gecko")
+            && !ua.contains("like gecko")
+            && !ua.contains("webkit")
+            && !ua.contains("safari")
+        {
+            Self::Gecko
+        } else if ua.contains("applewebkit") {
+            Self::WebKit
+        } else {
+            Self::Unknown
+        }
+    }
 
-#[allow(dead_code)]
-pub(crate) fn is_gecko() -> bool {
-    let ua = user_agent();
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Blink => "Blink",
+            Self::Gecko => "Gecko",
+            Self::WebKit => "WebKit",
+            Self::Unknown => "unknown engine",
+        }
+    }
+}
 
-    ua.contains("gecko")
-        && !ua.contains("like gecko")
-        && !ua.contains("webkit")
-        && !ua.contains("chrome")
-        && !ua.contains("safari")
+#[cfg(target_arch = "wasm32")]
+fn user_agent() -> Option<String> {
+    web_sys::window()?.navigator().user_agent().ok()
 }
 
-pub(crate) fn is_blink() -> bool {
-    let ua = user_agent();
-    ua.contains("chrome")
+#[cfg(test)]
+mod tests {
+    use super::BrowserEngine;
+
+    const SAFARI: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1.2 Safari/605.1.15";
+    const SAFARI_TECHNICAL_PREVIEW: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Safari/605.1.15";
+    const FIREFOX: &str =
+        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:123.0) Gecko/20100101 Firefox/123.0";
+    const CHROME: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36";
+    const CHROMIUM: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36";
+
+    #[test]
+    fn detects_webkit() {
+        assert_eq!(
+            BrowserEngine::from_user_agent(SAFARI),
+            BrowserEngine::WebKit
+        );
+        assert_eq!(
+            BrowserEngine::from_user_agent(SAFARI_TECHNICAL_PREVIEW),
+            BrowserEngine::WebKit
+        );
+    }
+
+    #[test]
+    fn detects_gecko() {
+        assert_eq!(
+            BrowserEngine::from_user_agent(FIREFOX),
+            BrowserEngine::Gecko
+        );
+    }
+
+    #[test]
+    fn detects_blink() {
+        assert_eq!(BrowserEngine::from_user_agent(CHROME), BrowserEngine::Blink);
+        assert_eq!(
+            BrowserEngine::from_user_agent(CHROMIUM),
+            BrowserEngine::Blink
+        );
+    }
+
+    #[test]
+    fn unknown_user_agent() {
+        assert_eq!(BrowserEngine::from_user_agent(""), BrowserEngine::Unknown);
+    }
 }
@@ -3,3 +3,162 @@ pub(crate) fn rect_to_relative(rect: egui::Rect, parent: egui::Rect) -> egui::Re
     let max = rect.max - parent.min;
     egui::Rect::from_min_max(min.to_pos2(), max.to_pos2())
 }
+
+/// Splits the union of the given rects into non-overlapping rects.
+pub(crate) fn disjoint_union(rects: &[egui::Rect]) -> Vec<egui::Rect> {
+    let mut ys: Vec<f32> = rects.iter().flat_map(|r| [r.min.y, r.max.y]).collect();
+    ys.sort_by(f32::total_cmp);
+    ys.dedup();
+
+    let mut result = Vec::new();
+
+    for band in ys.windows(2) {
+        let (top, bottom) = (band[0], band[1]);
+
+        let mut spans: Vec<_> = rects
+            .iter()
+            .filter(|r| r.min.y <= top && r.max.y >= bottom)
+            .map(|r| (r.min.x, r.max.x))
+            .collect();
+        spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut merged: Vec<(f32, f32)> = Vec::new();
+        for (left, right) in spans {
+            match merged.last_mut() {
+                Some(last) if left <= last.1 => last.1 = last.1.max(right),
+                _ => merged.push((left, right)),
+            }
+        }
+
+        result.extend(merged.into_iter().map(|(left, right)| {
+            egui::Rect::from_min_max(egui::pos2(left, top), egui::pos2(right, bottom))
+        }));
+    }
+
+    result
+}
+
+/// A shape in CSS pixels, relative to an HTML element.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Shape {
+    RoundedRect(egui::Rect, egui::Rounding),
+    Polygon(Vec<egui::Pos2>),
+}
+
+impl Shape {
+    pub(crate) fn bounding_rect(&self) -> egui::Rect {
+        match self {
+            Self::RoundedRect(rect, _) => *rect,
+            Self::Polygon(points) => egui::Rect::from_points(points),
+        }
+    }
+
+    /// The part of the shape inside `clip`, if any.
+    ///
+    /// Rounded corners cut by `clip` become square, which is exact unless the
+    /// cut goes through the rounding of another corner.
+    pub(crate) fn intersect(&self, clip: egui::Rect) -> Option<Self> {
+        let shape = match self {
+            Self::RoundedRect(rect, rounding) => {
+                let inside = |corner: egui::Pos2, radius: f32| {
+                    if clip.contains(corner) {
+                        radius
+                    } else {
+                        0.0
+                    }
+                };
+                let rounding = egui::Rounding {
+                    nw: inside(rect.left_top(), rounding.nw),
+                    ne: inside(rect.right_top(), rounding.ne),
+                    sw: inside(rect.left_bottom(), rounding.sw),
+                    se: inside(rect.right_bottom(), rounding.se),
+                };
+
+                Self::RoundedRect(rect.intersect(clip), rounding)
+            }
+            Self::Polygon(points) => Self::Polygon(clip_polygon(points, clip)),
+        };
+
+        let rect = shape.bounding_rect();
+        (rect.is_positive() && !matches!(&shape, Self::Polygon(points) if points.len() < 3))
+            .then_some(shape)
+    }
+
+    /// SVG path data tracing the shape, also valid inside CSS `path()`.
+    pub(crate) fn to_path_data(&self) -> String {
+        match self {
+            Self::RoundedRect(rect, rounding) => rounded_rect_path_data(*rect, *rounding),
+            Self::Polygon(points) => {
+                let mut path = points
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| format!("{}{},{}", if i == 0 { 'M' } else { 'L' }, p.x, p.y))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                path.push_str(" z");
+                path
+            }
+        }
+    }
+}
+
+fn rounded_rect_path_data(rect: egui::Rect, rounding: egui::Rounding) -> String {
+    let max_radius = rect.width().min(rect.height()) / 2.0;
+    let egui::Rounding { nw, ne, sw, se } = rounding.at_least(0.0).at_most(max_radius);
+    let egui::Rect { min, max } = rect;
+
+    let corner = |radius: f32, x: f32, y: f32| {
+        if radius > 0.0 {
+            format!(" A{radius},{radius} 0 0 1 {x},{y}")
+        } else {
+            String::new()
+        }
+    };
+
+    format!(
+        "M{},{} H{}{} V{}{} H{}{} V{}{} z",
+        min.x + nw,
+        min.y,
+        max.x - ne,
+        corner(ne, max.x, min.y + ne),
+        max.y - se,
+        corner(se, max.x - se, max.y),
+        min.x + sw,
+        corner(sw, min.x, max.y - sw),
+        min.y + nw,
+        corner(nw, min.x + nw, min.y),
+    )
+}
+
+/// Sutherland–Hodgman clipping of a polygon against a rect.
+fn clip_polygon(points: &[egui::Pos2], clip: egui::Rect) -> Vec<egui::Pos2> {
+    // Signed distance to each edge, positive inside.
+    let edges: [&dyn Fn(egui::Pos2) -> f32; 4] = [
+        &|p| p.x - clip.min.x,
+        &|p| clip.max.x - p.x,
+        &|p| p.y - clip.min.y,
+        &|p| clip.max.y - p.y,
+    ];
+
+    let mut points = points.to_vec();
+
+    for distance in edges {
+        let input = std::mem::take(&mut points);
+
+        for (i, &current) in input.iter().enumerate() {
+            let previous = input[(i + input.len() - 1) % input.len()];
+            let (d_current, d_previous) = (distance(current), distance(previous));
+
+            if (d_current >= 0.0) != (d_previous >= 0.0) {
+                let t = d_previous / (d_previous - d_current);
+                points.push(previous + (current - previous) * t);
+            }
+
+            if d_current >= 0.0 {
+                points.push(current);
+            }
+        }
+    }
+
+    points
+}
@@ -0,0 +1,1023 @@
+use hframe::{
+    dom_backends::{DomOperation, Fake},
+    Aware,
+};
+
+fn setup() -> (egui::Context, Fake) {
+    let ctx = egui::Context::default();
+    let dom = Fake::new();
+    hframe::set_dom_backend(&ctx, dom.clone());
+    hframe::set_composition_strategy(&ctx, hframe::composition_strategies::ClipPath::new());
+    (ctx, dom)
+}
+
+fn run_frame(ctx: &egui::Context, mut ui: impl FnMut(&egui::Context)) -> egui::FullOutput {
+    let input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(1024.0, 768.0),
+        )),
+        ..Default::default()
+    };
+
+    ctx.run(input, |ctx| {
+        ui(ctx);
+        hframe::sync(ctx);
+    })
+}
+
+fn replacements(operations: &[DomOperation], id: &str) -> usize {
+    operations
+        .iter()
+        .filter(|op| matches!(op, DomOperation::ReplaceElement { id: i, .. } if i == id))
+        .count()
+}
+
+#[test]
+fn creates_the_element_once() {
+    let (ctx, dom) = setup();
+
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Counter")
+            .content("<p>0</p>")
+            .show(ctx);
+    });
+
+    let operations = dom.take_operations();
+    assert!(operations.contains(&DomOperation::CreateElement {
+        id: "counter".into()
+    }));
+    assert_eq!(replacements(&operations, "counter"), 1);
+    assert!(dom.has_element("counter"));
+
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Counter")
+            .content("<p>0</p>")
+            .show(ctx);
+    });
+
+    let operations = dom.take_operations();
+    assert_eq!(replacements(&operations, "counter"), 0);
+    assert!(operations.iter().any(
+        |op| matches!(op, DomOperation::SetAttribute { id, name, .. } if id == "counter" && name == "style")
+    ));
+}
+
+#[test]
+fn re_renders_when_content_changes() {
+    let (ctx, dom) = setup();
+
+    for count in 0..3 {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Counter")
+                .content(&format!("<p>{count}</p>"))
+                .show(ctx);
+        });
+    }
+
+    let operations = dom.take_operations();
+    // Rendered once and then patched, keeping the element alive.
+    assert_eq!(replacements(&operations, "counter"), 1);
+    assert!(matches!(
+        operations.iter().rev().find(|op| matches!(op, DomOperation::PatchElement { .. })),
+        Some(DomOperation::PatchElement { outer_html, .. }) if outer_html.contains("<p>2</p>")
+    ));
+}
+
+#[test]
+fn removes_the_element_when_not_shown() {
+    let (ctx, dom) = setup();
+
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Video")
+            .content("<video></video>")
+            .show(ctx);
+    });
+    assert!(dom.has_element("video"));
+
+    run_frame(&ctx, |_ctx| {});
+
+    assert!(dom
+        .take_operations()
+        .contains(&DomOperation::RemoveElement { id: "video".into() }));
+    assert!(!dom.has_element("video"));
+}
+
+#[test]
+fn punches_holes_for_aware_windows_on_top() {
+    let (ctx, dom) = setup();
+    let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1024.0, 768.0));
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Iframe")
+                .content("<iframe></iframe>")
+                .show(ctx);
+
+            egui::Window::new("On top")
+                .fixed_rect(screen)
+                .show(ctx, |ui| ui.label("Egui"))
+                .aware();
+        });
+    }
+
+    let clip_path = dom
+        .take_operations()
+        .into_iter()
+        .rev()
+        .find_map(|op| match op {
+            DomOperation::SetStyleProperty {
+                id,
+                property,
+                value,
+            } if id == "iframe" && property == "clip-path" => Some(value),
+            _ => None,
+        });
+
+    let clip_path = clip_path.expect("Clip path was not applied");
+    // The outer rect plus the hole covering it.
+    assert_eq!(clip_path.matches('M').count(), 2);
+}
+
+#[test]
+fn recreates_elements_removed_by_third_parties() {
+    let (ctx, dom) = setup();
+    let errors = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let errors_clone = errors.clone();
+    hframe::set_error_callback(&ctx, move |err| errors_clone.lock().unwrap().push(err));
+
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Iframe")
+            .content("<iframe></iframe>")
+            .show(ctx);
+    });
+
+    dom.detach_element("iframe");
+    dom.take_operations();
+
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Iframe")
+            .content("<iframe></iframe>")
+            .show(ctx);
+    });
+
+    assert!(dom
+        .take_operations()
+        .contains(&DomOperation::CreateElement {
+            id: "iframe".into()
+        }));
+    assert_eq!(
+        *errors.lock().unwrap(),
+        vec![hframe::HframeError::ElementNotFound("iframe".into())]
+    );
+}
+
+fn style_left(operations: &[DomOperation], id: &str) -> f32 {
+    operations
+        .iter()
+        .rev()
+        .find_map(|op| match op {
+            DomOperation::SetAttribute {
+                id: i, name, value, ..
+            } if i == id && name == "style" => {
+                let left = value.split("left: ").nth(1)?.split("px").next()?;
+                left.parse().ok()
+            }
+            _ => None,
+        })
+        .expect("Style was not set")
+}
+
+#[test]
+fn positions_html_relative_to_the_canvas() {
+    let (ctx, dom) = setup();
+    let show = |ctx: &egui::Context| {
+        hframe::HtmlWindow::new("Counter")
+            .content("<p>0</p>")
+            .show(ctx);
+    };
+
+    run_frame(&ctx, show);
+    run_frame(&ctx, show);
+    let left = style_left(&dom.take_operations(), "counter");
+
+    dom.set_canvas_rect(Some(egui::Rect::from_min_size(
+        egui::pos2(100.0, 50.0),
+        egui::vec2(1024.0, 768.0),
+    )));
+    run_frame(&ctx, show);
+    run_frame(&ctx, show);
+
+    assert_eq!(style_left(&dom.take_operations(), "counter"), left + 100.0);
+}
+
+#[test]
+fn scales_html_with_the_canvas() {
+    let (ctx, dom) = setup();
+    let show = |ctx: &egui::Context| {
+        hframe::HtmlWindow::new("Counter")
+            .content("<p>0</p>")
+            .show(ctx);
+    };
+
+    run_frame(&ctx, show);
+    run_frame(&ctx, show);
+    let left = style_left(&dom.take_operations(), "counter");
+
+    // Canvas displayed at twice the size egui thinks it has.
+    dom.set_canvas_rect(Some(egui::Rect::from_min_size(
+        egui::Pos2::ZERO,
+        egui::vec2(2048.0, 1536.0),
+    )));
+    run_frame(&ctx, show);
+    run_frame(&ctx, show);
+
+    assert_eq!(style_left(&dom.take_operations(), "counter"), left * 2.0);
+}
+
+fn last_clip_path(operations: &[DomOperation], id: &str) -> String {
+    operations
+        .iter()
+        .rev()
+        .find_map(|op| match op {
+            DomOperation::SetStyleProperty {
+                id: i,
+                property,
+                value,
+            } if i == id && property == "clip-path" => Some(value.clone()),
+            _ => None,
+        })
+        .expect("Clip path was not applied")
+}
+
+#[test]
+fn widgets_sharing_a_layer_do_not_punch_holes_in_each_other() {
+    let (ctx, dom) = setup();
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            egui::Window::new("Widgets")
+                .show(ctx, |ui| {
+                    ui.add(hframe::Html::new("first", "<p>1</p>").size([200.0, 100.0]));
+                    ui.add(hframe::Html::new("second", "<p>2</p>").size([200.0, 100.0]));
+                })
+                .aware();
+        });
+    }
+
+    let operations = dom.take_operations();
+    assert!(dom.has_element("first"));
+    assert!(dom.has_element("second"));
+    assert_eq!(last_clip_path(&operations, "first").matches('M').count(), 1);
+    assert_eq!(
+        last_clip_path(&operations, "second").matches('M').count(),
+        1
+    );
+}
+
+#[test]
+fn clips_widgets_inside_scroll_areas() {
+    let (ctx, dom) = setup();
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            egui::Window::new("Scroll").show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(100.0)
+                    .show(ui, |ui| {
+                        ui.add(hframe::Html::new("tall", "<p>tall</p>").size([200.0, 400.0]));
+                    });
+            });
+        });
+    }
+
+    let clip_path = last_clip_path(&dom.take_operations(), "tall");
+    assert!(!clip_path.contains("V400 "), "{clip_path}");
+}
+
+#[test]
+fn windows_float_over_html_panels() {
+    use hframe::HtmlPanel;
+
+    let (ctx, dom) = setup();
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            egui::CentralPanel::default().show_html(ctx, "map", "<div>map</div>");
+            hframe::HtmlWindow::new("Counter")
+                .content("<p>0</p>")
+                .show(ctx);
+        });
+    }
+
+    let operations = dom.take_operations();
+    assert!(dom.has_element("map"));
+    assert_eq!(last_clip_path(&operations, "map").matches('M').count(), 2);
+    assert_eq!(
+        last_clip_path(&operations, "counter").matches('M').count(),
+        1
+    );
+}
+
+fn last_style(operations: &[DomOperation], id: &str) -> String {
+    operations
+        .iter()
+        .rev()
+        .find_map(|op| match op {
+            DomOperation::SetAttribute {
+                id: i, name, value, ..
+            } if i == id && name == "style" => Some(value.clone()),
+            _ => None,
+        })
+        .expect("Style was not set")
+}
+
+#[test]
+fn hides_html_of_collapsed_windows() {
+    let (ctx, dom) = setup();
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Collapsed")
+                .content("<p>hidden</p>")
+                .default_open(false)
+                .show(ctx);
+        });
+    }
+
+    assert!(last_style(&dom.take_operations(), "collapsed").contains("visibility: hidden"));
+}
+
+#[test]
+fn places_html_inside_fixed_windows() {
+    let (ctx, dom) = setup();
+    let window_rect = egui::Rect::from_min_size(egui::pos2(100.0, 100.0), egui::vec2(300.0, 200.0));
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Fixed")
+                .content("<p>fixed</p>")
+                .fixed_rect(window_rect)
+                .title_bar(false)
+                .show(ctx);
+        });
+    }
+
+    let left = style_left(&dom.take_operations(), "fixed");
+    assert!(left >= window_rect.left() && left < window_rect.center().x);
+}
+
+#[test]
+fn reports_html_state_in_the_window_response() {
+    let (ctx, _dom) = setup();
+    let mut responses = Vec::new();
+
+    for content in ["<p>0</p>", "<p>0</p>", "<p>1</p>"] {
+        run_frame(&ctx, |ctx| {
+            responses.push(
+                hframe::HtmlWindow::new("Counter")
+                    .content(content)
+                    .show(ctx)
+                    .expect("Window should be open")
+                    .inner,
+            );
+        });
+    }
+
+    let rerendered: Vec<_> = responses.iter().map(|r| r.rerendered).collect();
+    assert_eq!(rerendered, [true, false, true]);
+    assert!(responses.iter().all(|r| r.visible && r.html_rect.is_some()));
+
+    let mut open = false;
+    run_frame(&ctx, |ctx| {
+        assert!(hframe::HtmlWindow::new("Counter")
+            .open(&mut open)
+            .show(ctx)
+            .is_none());
+    });
+}
+
+#[test]
+fn places_html_after_custom_widgets() {
+    let (ctx, _dom) = setup();
+    let mut plain = None;
+    let mut with_toolbar = None;
+    let mut without_slot = None;
+
+    run_frame(&ctx, |ctx| {
+        plain = hframe::HtmlWindow::new("Plain")
+            .content("<p>plain</p>")
+            .fixed_rect(egui::Rect::from_min_size(
+                egui::pos2(0.0, 0.0),
+                egui::vec2(200.0, 200.0),
+            ))
+            .show(ctx);
+
+        with_toolbar = hframe::HtmlWindow::new("Toolbar")
+            .content("<p>toolbar</p>")
+            .fixed_rect(egui::Rect::from_min_size(
+                egui::pos2(300.0, 0.0),
+                egui::vec2(200.0, 200.0),
+            ))
+            .show_with(ctx, |ui, html_slot| {
+                let _ = ui.button("Reload");
+                html_slot.show(ui);
+            });
+
+        without_slot = hframe::HtmlWindow::new("No slot")
+            .content("<p>hidden</p>")
+            .show_with(ctx, |ui, _html_slot| {
+                ui.label("Only egui");
+            });
+    });
+
+    let plain = plain.unwrap().inner.html_rect.unwrap();
+    let with_toolbar = with_toolbar.unwrap().inner.html_rect.unwrap();
+    assert!(with_toolbar.top() > plain.top());
+    assert!(with_toolbar.height() < plain.height());
+
+    let without_slot = without_slot.unwrap().inner;
+    assert!(!without_slot.visible);
+    assert!(without_slot.html_rect.is_none());
+}
+
+#[test]
+fn auto_aware_punches_holes_for_every_egui_area() {
+    let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1024.0, 768.0));
+
+    for auto_aware in [false, true] {
+        let (ctx, dom) = setup();
+        hframe::set_auto_aware(&ctx, auto_aware);
+
+        for _ in 0..2 {
+            run_frame(&ctx, |ctx| {
+                hframe::HtmlWindow::new("Iframe")
+                    .content("<iframe></iframe>")
+                    .show(ctx);
+
+                egui::Window::new("Not aware")
+                    .fixed_rect(screen)
+                    .show(ctx, |ui| ui.label("Egui"));
+            });
+        }
+
+        let holes = last_clip_path(&dom.take_operations(), "iframe")
+            .matches('M')
+            .count()
+            - 1;
+        assert_eq!(holes, usize::from(auto_aware));
+    }
+}
+
+#[test]
+fn punches_holes_for_aware_areas_and_popups() {
+    let (ctx, dom) = setup();
+    let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1024.0, 768.0));
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Iframe")
+                .content("<iframe></iframe>")
+                .fixed_rect(screen)
+                .show(ctx);
+
+            egui::Area::new("Floating")
+                .fixed_pos(egui::pos2(100.0, 100.0))
+                .show(ctx, |ui| ui.label("Egui"))
+                .aware();
+
+            // Stands for the popups opened by tooltips, combo boxes and menus.
+            egui::Area::new("Popup")
+                .order(egui::Order::Foreground)
+                .fixed_pos(egui::pos2(500.0, 500.0))
+                .show(ctx, |ui| ui.label("Popup"));
+
+            egui::CentralPanel::default().show(ctx, |ui| ui.label("Panel").aware());
+        });
+    }
+
+    let holes = last_clip_path(&dom.take_operations(), "iframe")
+        .matches('M')
+        .count()
+        - 1;
+    assert_eq!(holes, 2);
+}
+
+#[test]
+fn holes_follow_the_shape_of_areas() {
+    let (ctx, dom) = setup();
+    let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1024.0, 768.0));
+    let triangle = vec![
+        egui::pos2(600.0, 100.0),
+        egui::pos2(700.0, 200.0),
+        egui::pos2(500.0, 200.0),
+    ];
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Iframe")
+                .content("<iframe></iframe>")
+                .fixed_rect(screen)
+                .show(ctx);
+
+            egui::Window::new("Rounded")
+                .fixed_pos(egui::pos2(100.0, 100.0))
+                .show(ctx, |ui| ui.label("Egui"))
+                .aware();
+
+            egui::Area::new("Triangle")
+                .fixed_pos(egui::pos2(500.0, 100.0))
+                .show(ctx, |ui| ui.allocate_space(egui::vec2(200.0, 100.0)))
+                .aware_with_shape(hframe::AreaShape::Polygon(triangle.clone()));
+        });
+    }
+
+    let clip_path = last_clip_path(&dom.take_operations(), "iframe");
+    let rounding = ctx.style().visuals.window_rounding.nw;
+    assert!(
+        clip_path.contains(&format!("A{rounding},{rounding}")),
+        "{clip_path}"
+    );
+    assert_eq!(clip_path.matches(" L").count(), 2, "{clip_path}");
+}
+
+#[test]
+fn holes_follow_custom_window_frames() {
+    let (ctx, dom) = setup();
+    let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1024.0, 768.0));
+
+    for _ in 0..2 {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Iframe")
+                .content("<iframe></iframe>")
+                .fixed_rect(screen)
+                .show(ctx);
+
+            hframe::HtmlWindow::new("Custom")
+                .content("<p>Custom</p>")
+                .frame(egui::Frame::window(&ctx.style()).rounding(13.0))
+                .title_bar(false)
+                .fixed_pos(egui::pos2(100.0, 100.0))
+                .show(ctx);
+        });
+    }
+
+    let clip_path = last_clip_path(&dom.take_operations(), "iframe");
+    assert!(clip_path.contains("A13,13"), "{clip_path}");
+}
+
+/// Top of the first hole in a clip path.
+fn first_hole_top(clip_path: &str) -> f32 {
+    let subpath = clip_path.split('M').nth(2).expect("No hole found");
+    let y = subpath.split([',', ' ']).nth(1).unwrap();
+    y.parse().unwrap()
+}
+
+#[test]
+fn shadow_holes_cover_window_shadows() {
+    let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1024.0, 768.0));
+
+    let clip_paths = [false, true].map(|shadow_holes| {
+        let (ctx, dom) = setup();
+        hframe::set_shadow_holes(&ctx, shadow_holes);
+
+        for _ in 0..2 {
+            run_frame(&ctx, |ctx| {
+                hframe::HtmlWindow::new("Iframe")
+                    .content("<iframe></iframe>")
+                    .fixed_rect(screen)
+                    .show(ctx);
+
+                egui::Window::new("Shadowed")
+                    .fixed_pos(egui::pos2(100.0, 100.0))
+                    .show(ctx, |ui| ui.label("Egui"))
+                    .aware();
+            });
+        }
+
+        last_clip_path(&dom.take_operations(), "iframe")
+    });
+
+    let style = egui::Context::default().style();
+    let shadow = style.visuals.window_shadow.extrusion;
+    assert_eq!(
+        first_hole_top(&clip_paths[0]) - first_hole_top(&clip_paths[1]),
+        shadow
+    );
+
+    // Corners grow with the hole, following the rounded shadow.
+    let radius = style.visuals.window_rounding.nw + shadow;
+    assert!(
+        clip_paths[1].contains(&format!("A{radius},{radius}")),
+        "{}",
+        clip_paths[1]
+    );
+}
+
+#[test]
+fn keeps_elements_alive_while_windows_are_closed() {
+    let (ctx, dom) = setup();
+    let mut open = true;
+
+    let show = |ctx: &egui::Context, open: &mut bool| {
+        hframe::HtmlWindow::new("Video")
+            .content("<video></video>")
+            .keep_alive(true)
+            .open(open)
+            .show(ctx);
+    };
+
+    run_frame(&ctx, |ctx| show(ctx, &mut open));
+    open = false;
+    run_frame(&ctx, |ctx| show(ctx, &mut open));
+
+    let operations = dom.take_operations();
+    assert!(operations.contains(&DomOperation::SetStyleProperty {
+        id: "video".into(),
+        property: "display".into(),
+        value: "none".into(),
+    }));
+    assert!(dom.has_element("video"));
+
+    open = true;
+    run_frame(&ctx, |ctx| show(ctx, &mut open));
+
+    let operations = dom.take_operations();
+    assert_eq!(replacements(&operations, "video"), 0);
+    assert!(!operations
+        .iter()
+        .any(|op| matches!(op, DomOperation::RemoveElement { .. })));
+}
+
+#[test]
+fn evicts_kept_alive_elements() {
+    let (ctx, dom) = setup();
+
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Video")
+            .content("<video></video>")
+            .keep_alive(true)
+            .show(ctx);
+    });
+    run_frame(&ctx, |_ctx| {});
+    assert!(dom.has_element("video"));
+
+    hframe::evict(&ctx, "video");
+    assert!(!dom.has_element("video"));
+}
+
+#[test]
+fn posts_messages_without_re_rendering() {
+    let (ctx, dom) = setup();
+
+    for count in 0..2 {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Counter")
+                .content("<p>0</p>")
+                .show(ctx);
+
+            hframe::post_message(ctx, "counter", &[count]).unwrap();
+        });
+    }
+
+    let operations = dom.take_operations();
+    assert_eq!(replacements(&operations, "counter"), 1);
+    assert!(operations.contains(&DomOperation::PostMessage {
+        id: "counter".into(),
+        json: "[1]".into(),
+    }));
+}
+
+#[test]
+fn receives_messages_from_allowed_origins() {
+    let (ctx, dom) = setup();
+    let show = |ctx: &egui::Context| {
+        hframe::HtmlWindow::new("Form")
+            .content("<iframe></iframe>")
+            .allowed_origin("https://trusted.com")
+            .show(ctx);
+    };
+
+    run_frame(&ctx, show);
+
+    for (origin, value) in [
+        (None, 1),
+        (Some("https://trusted.com"), 2),
+        (Some("https://evil.com"), 3),
+    ] {
+        dom.send_message(hframe::HtmlMessage {
+            id: "form".into(),
+            origin: origin.map(String::from),
+            json: value.to_string(),
+        });
+    }
+
+    run_frame(&ctx, show);
+
+    let mut messages = Vec::new();
+    run_frame(&ctx, |ctx| {
+        show(ctx);
+        messages = hframe::take_messages::<i32>(ctx, "form");
+    });
+    assert_eq!(messages, [1, 2]);
+
+    // Unread messages only last a frame.
+    run_frame(&ctx, |ctx| {
+        show(ctx);
+        messages = hframe::take_messages::<i32>(ctx, "form");
+    });
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn widgets_receive_messages_from_allowed_origins() {
+    let (ctx, dom) = setup();
+    let show = |ctx: &egui::Context| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.add(
+                hframe::Html::new("map", "<iframe></iframe>").allowed_origin("https://maps.com"),
+            );
+        });
+    };
+
+    run_frame(&ctx, show);
+    dom.send_message(hframe::HtmlMessage {
+        id: "map".into(),
+        origin: Some("https://maps.com".into()),
+        json: "1".into(),
+    });
+    run_frame(&ctx, show);
+
+    let mut messages = Vec::new();
+    run_frame(&ctx, |ctx| {
+        show(ctx);
+        messages = hframe::take_messages::<i32>(ctx, "map");
+    });
+    assert_eq!(messages, [1]);
+}
+
+#[test]
+fn forwards_dom_events() {
+    let (ctx, dom) = setup();
+    let show = |ctx: &egui::Context| {
+        hframe::HtmlWindow::new("Form")
+            .content(r#"<form><input name="email"></form>"#)
+            .show(ctx);
+    };
+
+    let submit = hframe::HtmlEvent {
+        kind: hframe::HtmlEventKind::Submit,
+        target_id: None,
+        target_name: None,
+        value: None,
+        checked: None,
+        form_data: vec![("email".into(), "me@example.com".into())],
+    };
+
+    run_frame(&ctx, show);
+    dom.dispatch_event("form", &submit);
+    dom.dispatch_event("unknown", &submit);
+    run_frame(&ctx, show);
+
+    let mut events = Vec::new();
+    let mut unknown_events = Vec::new();
+    run_frame(&ctx, |ctx| {
+        show(ctx);
+        events = hframe::take_events(ctx, "form");
+        unknown_events = hframe::take_events(ctx, "unknown");
+    });
+
+    assert_eq!(events, [submit]);
+    assert!(unknown_events.is_empty());
+}
+
+#[test]
+fn navigates_iframes_without_re_rendering() {
+    let (ctx, dom) = setup();
+
+    for url in ["https://example.com/a", "https://example.com/b"] {
+        run_frame(&ctx, |ctx| {
+            hframe::HtmlWindow::new("Browser").url(url).show(ctx);
+        });
+    }
+
+    let operations = dom.take_operations();
+    assert_eq!(replacements(&operations, "browser"), 1);
+    assert!(operations.iter().any(|op| matches!(
+        op,
+        DomOperation::PatchElement { id, outer_html }
+            if id == "browser" && outer_html.contains(r#"src="https://example.com/b""#)
+    )));
+}
+
+#[test]
+fn tracks_iframe_load_state() {
+    let (ctx, dom) = setup();
+    let show = |ctx: &egui::Context, iframe: hframe::Iframe| {
+        let mut load_state = None;
+        let mut events = Vec::new();
+        run_frame(ctx, |ctx| {
+            load_state = hframe::HtmlWindow::new("Browser")
+                .iframe(iframe.clone())
+                .loading_indicator(true)
+                .show(ctx)
+                .and_then(|response| response.inner.load_state);
+            events = hframe::take_events(ctx, "browser");
+        });
+        (load_state, events)
+    };
+    let a = hframe::Iframe::new("https://example.com/a");
+    let b = hframe::Iframe::new("https://example.com/b");
+
+    let (load_state, _) = show(&ctx, a.clone());
+    assert_eq!(load_state, Some(hframe::LoadState::Loading));
+
+    dom.report_status("browser", &hframe::HtmlStatus::Load);
+    show(&ctx, a.clone());
+    let (load_state, events) = show(&ctx, a.clone());
+    assert_eq!(load_state, Some(hframe::LoadState::Loaded));
+    // Only used by hframe.
+    assert!(events.is_empty());
+
+    // Patching attributes other than the source doesn't reload.
+    let (load_state, _) = show(&ctx, a.clone().title("A"));
+    assert_eq!(load_state, Some(hframe::LoadState::Loaded));
+
+    // Navigating loads again.
+    let (load_state, _) = show(&ctx, b.clone());
+    assert_eq!(load_state, Some(hframe::LoadState::Loading));
+
+    dom.report_status("browser", &hframe::HtmlStatus::Load);
+    show(&ctx, b.clone());
+    dom.take_operations();
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Browser")
+            .iframe(b.clone())
+            .show(ctx);
+        hframe::navigate(ctx, "browser", hframe::Navigation::Reload);
+    });
+    assert_eq!(replacements(&dom.take_operations(), "browser"), 0);
+    let (load_state, _) = show(&ctx, b);
+    assert_eq!(load_state, Some(hframe::LoadState::Loading));
+}
+
+#[test]
+fn detects_failed_iframe_loads() {
+    let (ctx, dom) = setup();
+    let show = |ctx: &egui::Context, url: &str| {
+        let mut load_state = None;
+        run_frame(ctx, |ctx| {
+            load_state = hframe::HtmlWindow::new("Browser")
+                .url(url)
+                .show(ctx)
+                .and_then(|response| response.inner.load_state);
+        });
+        load_state
+    };
+
+    show(&ctx, "https://example.com/a");
+    dom.report_status("browser", &hframe::HtmlStatus::Error);
+    show(&ctx, "https://example.com/a");
+    let load_state = show(&ctx, "https://example.com/a");
+    assert!(matches!(load_state, Some(hframe::LoadState::Failed(_))));
+
+    // Loads not finished in time fail too.
+    let load_state = show(&ctx, "https://example.com/b");
+    assert_eq!(load_state, Some(hframe::LoadState::Loading));
+    hframe::set_load_timeout(&ctx, std::time::Duration::ZERO);
+    show(&ctx, "https://example.com/b");
+    let load_state = show(&ctx, "https://example.com/b");
+    assert!(matches!(load_state, Some(hframe::LoadState::Failed(_))));
+}
+
+#[test]
+fn shows_navigation_bars_only_for_iframes() {
+    for iframe in [true, false] {
+        let (ctx, _dom) = setup();
+        let mut texts = Vec::new();
+
+        // Windows are invisible on their first frame.
+        for _ in 0..2 {
+            texts = painted_texts(&run_frame(&ctx, |ctx| {
+                let window = hframe::HtmlWindow::new("Browser").navigation_bar(true);
+                let window = match iframe {
+                    true => window.url("https://example.com/"),
+                    false => window.content("<p>Not an iframe</p>"),
+                };
+                window.show(ctx);
+            }));
+        }
+
+        assert_eq!(texts.contains(&"⟳".to_string()), iframe);
+    }
+}
+
+#[test]
+fn navigates_iframes_on_sync() {
+    let (ctx, dom) = setup();
+
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Browser")
+            .url("https://example.com/")
+            .navigation_bar(true)
+            .show(ctx);
+        hframe::navigate(ctx, "browser", hframe::Navigation::Reload);
+    });
+
+    let operations = dom.take_operations();
+    let navigate = DomOperation::Navigate {
+        id: "browser".into(),
+        navigation: hframe::Navigation::Reload,
+    };
+    // Navigated once the element exists.
+    assert_eq!(operations.last(), Some(&navigate));
+
+    run_frame(&ctx, |ctx| {
+        hframe::HtmlWindow::new("Browser")
+            .url("https://example.com/")
+            .show(ctx);
+    });
+
+    assert!(!dom.take_operations().contains(&navigate));
+}
+
+fn painted_texts(output: &egui::FullOutput) -> Vec<String> {
+    output
+        .shapes
+        .iter()
+        .filter_map(|clipped| match &clipped.shape {
+            egui::Shape::Text(text) => Some(text.galley.job.text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn shows_the_content_title_as_the_window_title() {
+    let (ctx, dom) = setup();
+    let show = |ctx: &egui::Context, url: &str| {
+        painted_texts(&run_frame(ctx, |ctx| {
+            hframe::HtmlWindow::new("Docs")
+                .url(url)
+                .title_from_content(true)
+                .show(ctx);
+        }))
+    };
+    let report_title = || {
+        dom.report_status(
+            "docs",
+            &hframe::HtmlStatus::Title {
+                title: "Example Domain".into(),
+            },
+        );
+    };
+
+    // Windows are invisible on their first frame.
+    show(&ctx, "https://example.com/");
+    assert!(show(&ctx, "https://example.com/").contains(&"Docs".to_string()));
+
+    report_title();
+    show(&ctx, "https://example.com/");
+
+    let texts = show(&ctx, "https://example.com/");
+    assert!(texts.contains(&"Example Domain".to_string()));
+    assert!(!texts.contains(&"Docs".to_string()));
+    // Same window and element, only the title changed.
+    assert_eq!(replacements(&dom.take_operations(), "docs"), 1);
+
+    // Documents that don't report a title, like cross-origin ones, clear it.
+    dom.report_status("docs", &hframe::HtmlStatus::Load);
+    show(&ctx, "https://example.com/");
+    assert!(show(&ctx, "https://example.com/").contains(&"Docs".to_string()));
+
+    // So does navigating.
+    report_title();
+    show(&ctx, "https://example.com/");
+    show(&ctx, "https://example.org/");
+    assert!(show(&ctx, "https://example.org/").contains(&"Docs".to_string()));
+}
+
+#[test]
+fn error_callbacks_may_call_hframe() {
+    let (ctx, dom) = setup();
+    let callback_ctx = ctx.clone();
+    hframe::set_error_callback(&ctx, move |err| {
+        if let hframe::HframeError::ElementNotFound(id) = err {
+            hframe::evict(&callback_ctx, &id);
+        }
+    });
+
+    let show = |ctx: &egui::Context| {
+        hframe::HtmlWindow::new("Iframe")
+            .content("<iframe></iframe>")
+            .keep_alive(true)
+            .show(ctx);
+    };
+
+    run_frame(&ctx, show);
+    dom.detach_element("iframe");
+    run_frame(&ctx, show);
+
+    // Evicted from the callback, after being recreated.
+    assert!(!dom.has_element("iframe"));
+}